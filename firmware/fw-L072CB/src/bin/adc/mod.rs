@@ -0,0 +1,200 @@
+use defmt::*;
+use embassy_stm32::adc::{Adc, SampleTime};
+use embassy_stm32::peripherals::ADC;
+#[cfg(feature = "rail-sense")]
+use embassy_stm32::peripherals::{PB0, PB1};
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Delay, Timer};
+
+use crate::faults;
+use crate::watchdog::{self, Task};
+
+// factory calibration values in system memory (RM0367, 14.10)
+const VREFINT_CAL_ADDRESS: usize = 0x1FF80078;
+const TS_CAL1_ADDRESS: usize = 0x1FF8007A;
+const TS_CAL2_ADDRESS: usize = 0x1FF8007E;
+
+// calibration values were taken at VDDA = 3.0V, 30°C and 130°C
+const CAL_VDDA_MV: u32 = 3000;
+const TS_CAL1_TEMP: i32 = 30;
+const TS_CAL2_TEMP: i32 = 130;
+
+#[cfg(feature = "rail-sense")]
+const ADC_MAX: u32 = 4095;
+
+// external dividers, VIN 100k/10k and 1V2 directly on the pin
+#[cfg(feature = "rail-sense")]
+const VIN_DIVIDER: u32 = 11;
+#[cfg(feature = "rail-sense")]
+const V1V2_DIVIDER: u32 = 1;
+
+// limits for alarms, temperatures in 1/16°C like the TMP sensors
+const MCU_TEMP_MIN: i32 = -40 * 16;
+const MCU_TEMP_MAX: i32 = 85 * 16;
+const MCU_TEMP_HYST: i32 = 5 * 16;
+const VDDA_MIN_MV: i32 = 3000;
+const VDDA_MAX_MV: i32 = 3600;
+const VDDA_HYST_MV: i32 = 50;
+#[cfg(feature = "rail-sense")]
+const VIN_MIN_MV: i32 = 10800;
+#[cfg(feature = "rail-sense")]
+const VIN_MAX_MV: i32 = 13200;
+#[cfg(feature = "rail-sense")]
+const VIN_HYST_MV: i32 = 200;
+#[cfg(feature = "rail-sense")]
+const V1V2_MAX_MV: i32 = 1500;
+#[cfg(feature = "rail-sense")]
+const V1V2_HYST_MV: i32 = 50;
+
+const OVERSAMPLING: u32 = 4;
+
+/// Latest supply and MCU readings.
+#[derive(Clone, Copy, Default)]
+pub struct AdcReadings {
+    pub vin_mv: u32,
+    pub v1v2_mv: u32,
+    pub vdda_mv: u32,
+    /// MCU temperature in 1/16°C
+    pub mcu_temp: i32,
+}
+
+pub static ADC_READINGS: Mutex<ThreadModeRawMutex, AdcReadings> = Mutex::new(AdcReadings {
+    vin_mv: 0,
+    v1v2_mv: 0,
+    vdda_mv: 0,
+    mcu_temp: 0,
+});
+
+/// Analog inputs of the supply rails.
+///
+/// The rails are only routed to the ADC on boards with the sense dividers fitted
+/// (`rail-sense` feature), otherwise PB0 and PB1 are unconnected.
+#[cfg(feature = "rail-sense")]
+pub struct RailPins {
+    pub vin: PB0,
+    pub v1v2: PB1,
+}
+
+#[cfg(not(feature = "rail-sense"))]
+pub struct RailPins;
+
+/// Read a 16-bit value from the given address.
+fn read_u16_at_address(address: usize) -> u16 {
+    unsafe { (address as *const u16).read_volatile() }
+}
+
+/// Get VDDA in mV from a VREFINT sample.
+fn vdda_mv(vrefint_raw: u32) -> u32 {
+    let vrefint_cal = read_u16_at_address(VREFINT_CAL_ADDRESS) as u32;
+    if vrefint_raw == 0 {
+        return 0;
+    }
+    CAL_VDDA_MV * vrefint_cal / vrefint_raw
+}
+
+/// Get the MCU temperature in 1/16°C from a temperature sensor sample.
+fn mcu_temp(ts_raw: u32, vdda_mv: u32) -> i32 {
+    let ts_cal1 = read_u16_at_address(TS_CAL1_ADDRESS) as i32;
+    let ts_cal2 = read_u16_at_address(TS_CAL2_ADDRESS) as i32;
+
+    // scale the sample to the calibration voltage
+    let ts = (ts_raw * vdda_mv / CAL_VDDA_MV) as i32;
+
+    (ts - ts_cal1) * (TS_CAL2_TEMP - TS_CAL1_TEMP) * 16 / (ts_cal2 - ts_cal1) + TS_CAL1_TEMP * 16
+}
+
+/// Convert a sample of an external channel into mV.
+#[cfg(feature = "rail-sense")]
+fn channel_mv(raw: u32, vdda_mv: u32, divider: u32) -> u32 {
+    raw * vdda_mv * divider / ADC_MAX
+}
+
+#[embassy_executor::task]
+pub async fn adc_manager(mut adc: Adc<'static, ADC>, _rails: RailPins) {
+    adc.set_sample_time(SampleTime::Cycles160_5);
+
+    #[cfg(feature = "rail-sense")]
+    let RailPins { mut vin, mut v1v2 } = _rails;
+
+    let mut vrefint = adc.enable_vref(&mut Delay);
+    let mut temperature = adc.enable_temperature(&mut Delay);
+
+    loop {
+        watchdog::check_in(Task::Adc);
+
+        let mut vrefint_raw = 0u32;
+        let mut ts_raw = 0u32;
+        #[cfg(feature = "rail-sense")]
+        let (mut vin_raw, mut v1v2_raw) = (0u32, 0u32);
+
+        for _ in 0..OVERSAMPLING {
+            vrefint_raw += adc.read(&mut vrefint).await as u32;
+            ts_raw += adc.read(&mut temperature).await as u32;
+            #[cfg(feature = "rail-sense")]
+            {
+                vin_raw += adc.read(&mut vin).await as u32;
+                v1v2_raw += adc.read(&mut v1v2).await as u32;
+            }
+        }
+
+        let vdda = vdda_mv(vrefint_raw / OVERSAMPLING);
+
+        let readings = AdcReadings {
+            #[cfg(feature = "rail-sense")]
+            vin_mv: channel_mv(vin_raw / OVERSAMPLING, vdda, VIN_DIVIDER),
+            #[cfg(feature = "rail-sense")]
+            v1v2_mv: channel_mv(v1v2_raw / OVERSAMPLING, vdda, V1V2_DIVIDER),
+            vdda_mv: vdda,
+            mcu_temp: mcu_temp(ts_raw / OVERSAMPLING, vdda),
+            ..Default::default()
+        };
+
+        debug!(
+            "adc: vin: {}mV, 1v2: {}mV, vdda: {}mV, mcu temp: {}",
+            readings.vin_mv, readings.v1v2_mv, readings.vdda_mv, readings.mcu_temp
+        );
+
+        *ADC_READINGS.lock().await = readings;
+
+        faults::check_limits(
+            faults::MCU_TEMP_OVER,
+            readings.mcu_temp,
+            MCU_TEMP_MIN,
+            MCU_TEMP_MAX,
+            MCU_TEMP_HYST,
+        )
+        .await;
+        faults::check_limits(
+            faults::VDDA_OUT_OF_RANGE,
+            readings.vdda_mv as i32,
+            VDDA_MIN_MV,
+            VDDA_MAX_MV,
+            VDDA_HYST_MV,
+        )
+        .await;
+
+        #[cfg(feature = "rail-sense")]
+        {
+            faults::check_limits(
+                faults::VIN_OUT_OF_RANGE,
+                readings.vin_mv as i32,
+                VIN_MIN_MV,
+                VIN_MAX_MV,
+                VIN_HYST_MV,
+            )
+            .await;
+            // the 1V2 rail is off most of the time, only over-voltage is a fault
+            faults::check_limits(
+                faults::V1V2_OUT_OF_RANGE,
+                readings.v1v2_mv as i32,
+                i32::MIN / 2,
+                V1V2_MAX_MV,
+                V1V2_HYST_MV,
+            )
+            .await;
+        }
+
+        Timer::after_millis(1000).await;
+    }
+}
@@ -0,0 +1,201 @@
+use core::fmt::Write;
+use core::mem::MaybeUninit;
+use core::panic::PanicInfo;
+use core::ptr::addr_of_mut;
+
+use defmt::*;
+use embassy_stm32::pac;
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::Mutex;
+use heapless::String;
+
+pub const MAX_MESSAGE: usize = 96;
+
+// reset causes as reported in `QResetInfo.cause`, the L0 has no separate
+// brown-out flag, brown-out resets are reported as power-on
+pub const RESET_POWER_ON: u32 = 1 << 0;
+pub const RESET_PIN: u32 = 1 << 1;
+pub const RESET_SOFTWARE: u32 = 1 << 2;
+pub const RESET_IWDG: u32 = 1 << 3;
+pub const RESET_WWDG: u32 = 1 << 4;
+pub const RESET_LOW_POWER: u32 = 1 << 5;
+pub const RESET_OPTION_BYTES: u32 = 1 << 6;
+pub const RESET_FIREWALL: u32 = 1 << 7;
+pub const RESET_PANIC: u32 = 1 << 8;
+
+const RECORD_MAGIC: u32 = 0x52535449;
+const PANIC_MAGIC: u32 = 0x50414e43;
+
+#[repr(C)]
+struct Record {
+    /// `RECORD_MAGIC` once initialised after power-on
+    magic: u32,
+    boots: u32,
+    /// `PANIC_MAGIC` while the message is valid
+    panicked: u32,
+    len: u32,
+    message: [u8; MAX_MESSAGE],
+}
+
+// not initialised at startup so it survives resets other than power-on
+#[link_section = ".uninit.RESET_RECORD"]
+static mut RESET_RECORD: MaybeUninit<Record> = MaybeUninit::uninit();
+
+/// # Safety
+///
+/// Only used at startup and in the panic handler, never concurrently.
+unsafe fn reset_record() -> &'static mut Record {
+    &mut *(addr_of_mut!(RESET_RECORD) as *mut Record)
+}
+
+/// Why and how often the MCU was started.
+pub struct ResetInfo {
+    /// `RESET_*` bits
+    pub cause: u32,
+    /// starts since the last power-on, including this one
+    pub boots: u32,
+    /// message and location of the panic before the last reset
+    pub panic: String<MAX_MESSAGE>,
+}
+
+pub static RESET_INFO: Mutex<ThreadModeRawMutex, ResetInfo> = Mutex::new(ResetInfo {
+    cause: 0,
+    boots: 0,
+    panic: String::new(),
+});
+
+/// Writes into a fixed buffer, cutting off what doesn't fit.
+struct Truncating<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for Truncating<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let n = s.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&s.as_bytes()[..n]);
+        self.len += n;
+        Ok(())
+    }
+}
+
+/// Read the reset cause from the RCC, clear it for the next start and
+/// pick up a panic recorded before the reset.
+fn read_reset_cause() -> u32 {
+    let csr = pac::RCC.csr().read();
+
+    let flags = [
+        (csr.porrstf(), RESET_POWER_ON),
+        (csr.pinrstf(), RESET_PIN),
+        (csr.sftrstf(), RESET_SOFTWARE),
+        (csr.iwdgrstf(), RESET_IWDG),
+        (csr.wwdgrstf(), RESET_WWDG),
+        (csr.lpwrrstf(), RESET_LOW_POWER),
+        (csr.oblrstf(), RESET_OPTION_BYTES),
+        (csr.fwrstf(), RESET_FIREWALL),
+    ];
+
+    pac::RCC.csr().modify(|w| w.set_rmvf(true));
+
+    flags
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |cause, (_, bit)| cause | bit)
+}
+
+/// Get the panic message of the previous run, if it ended with a panic.
+fn take_panic_message(record: &mut Record) -> Option<String<MAX_MESSAGE>> {
+    if record.panicked != PANIC_MAGIC {
+        return None;
+    }
+    record.panicked = 0;
+
+    let bytes = &record.message[..(record.len as usize).min(MAX_MESSAGE)];
+    // truncating may have split a character
+    let message = match core::str::from_utf8(bytes) {
+        Ok(message) => message,
+        Err(e) => core::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    };
+
+    let mut s = String::new();
+    let _ = s.push_str(message);
+    Some(s)
+}
+
+/// Collect the reset information, has to run once at startup.
+pub async fn init() {
+    let mut cause = read_reset_cause();
+    let record = unsafe { reset_record() };
+
+    if cause & RESET_POWER_ON != 0 || record.magic != RECORD_MAGIC {
+        record.magic = RECORD_MAGIC;
+        record.boots = 0;
+        record.panicked = 0;
+    }
+    record.boots = record.boots.wrapping_add(1);
+
+    let panic = take_panic_message(record);
+    if panic.is_some() {
+        cause |= RESET_PANIC;
+    }
+
+    info!("reset cause: {:x}, boots: {}", cause, record.boots);
+    if let Some(message) = &panic {
+        warn!("restarted after panic: {}", message.as_str());
+    }
+
+    *RESET_INFO.lock().await = ResetInfo {
+        cause,
+        boots: record.boots,
+        panic: panic.unwrap_or_default(),
+    };
+}
+
+/// Switch off the ASIC rail and run the fans at full speed.
+///
+/// Works on the registers directly, nothing of the HAL state can be trusted
+/// after a panic.
+fn safe_state() {
+    // RUN_1V2 (PA2) and LDO_EN (PA7) low, RESET (PB13) asserted
+    pac::GPIOA.bsrr().write(|w| {
+        w.set_br(2, true);
+        w.set_br(7, true);
+    });
+    pac::GPIOB.bsrr().write(|w| w.set_bs(13, true));
+
+    // full duty keeps the configured polarity of the fan outputs
+    pac::TIM2.ccr(0).write(|w| w.set_ccr(u16::MAX));
+    pac::TIM2.ccr(1).write(|w| w.set_ccr(u16::MAX));
+}
+
+fn record(info: &PanicInfo) {
+    // interrupts are disabled, nothing else runs anymore
+    let record = unsafe { reset_record() };
+
+    // a panic before `init` ran after power-on
+    if record.magic != RECORD_MAGIC {
+        record.magic = RECORD_MAGIC;
+        record.boots = 1;
+    }
+
+    let mut writer = Truncating {
+        buf: &mut record.message,
+        len: 0,
+    };
+    let _ = write!(writer, "{}", info);
+
+    record.len = writer.len as u32;
+    record.panicked = PANIC_MAGIC;
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    cortex_m::interrupt::disable();
+
+    safe_state();
+    record(info);
+    defmt::error!("{}", defmt::Display2Format(info));
+
+    // the ASICs stay off after the reboot until the host resets them
+    cortex_m::peripheral::SCB::sys_reset()
+}
@@ -0,0 +1,22 @@
+use defmt::*;
+use embassy_sync::blocking_mutex::raw::{CriticalSectionRawMutex, ThreadModeRawMutex};
+use embassy_sync::mutex::Mutex;
+use embassy_sync::signal::Signal;
+use embassy_time::Instant;
+use qaxe_core::events::{EventKind, EventLog};
+
+// events kept while no host is subscribed
+const BACKLOG: usize = 16;
+
+pub static EVENT_LOG: Mutex<ThreadModeRawMutex, EventLog<BACKLOG>> = Mutex::new(EventLog::new());
+
+/// Wakes up the control channel to send new events.
+pub static EVENT_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+/// Record an event for the host.
+pub async fn push(kind: EventKind, value: i32) {
+    let timestamp = Instant::now().as_millis() as u32;
+    let event = EVENT_LOG.lock().await.push(timestamp, kind, value);
+    info!("event {}: {} {:x}", event.seq, kind, value);
+    EVENT_SIGNAL.signal(());
+}
@@ -0,0 +1,88 @@
+use defmt::*;
+use embassy_stm32::gpio::Input;
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Duration, Ticker};
+use qaxe_core::events::EventKind;
+
+use crate::{events, pwm};
+
+// tach pulses are a few ms long at full speed, sample fast enough to see each
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+const SAMPLES_PER_SECOND: u32 = 1000;
+
+// two pulses per revolution
+const PULSES_PER_REV: u32 = 2;
+
+// a fan driven with at least this duty has to turn
+const STALL_MIN_DUTY: u16 = 200;
+const STALL_SECONDS: u32 = 3;
+
+/// Fan speeds in rpm, updated every second.
+pub static FAN_RPM: Mutex<ThreadModeRawMutex, [u32; 2]> = Mutex::new([0; 2]);
+
+/// Measure the fan speeds and report stalled fans.
+///
+/// Fans that were never seen turning since boot aren't checked, on boards
+/// with fans without tach output they would always look stalled.
+#[embassy_executor::task]
+pub async fn tach_monitor(tach1: Input<'static>, tach2: Input<'static>) {
+    let tachs = [tach1, tach2];
+    let mut ticker = Ticker::every(SAMPLE_INTERVAL);
+
+    let mut last = [true; 2];
+    let mut pulses = [0u32; 2];
+    let mut samples = 0;
+    let mut seen_turning = [false; 2];
+    let mut still_seconds = [0u32; 2];
+    let mut stalled = 0u32;
+
+    loop {
+        ticker.next().await;
+
+        for (i, tach) in tachs.iter().enumerate() {
+            let level = tach.is_high();
+            // count falling edges
+            if last[i] && !level {
+                pulses[i] += 1;
+            }
+            last[i] = level;
+        }
+
+        samples += 1;
+        if samples < SAMPLES_PER_SECOND {
+            continue;
+        }
+        samples = 0;
+
+        let rpm = pulses.map(|p| p * 60 / PULSES_PER_REV);
+        pulses = [0; 2];
+        *FAN_RPM.lock().await = rpm;
+
+        let applied = pwm::PWM_STATE.lock().await.applied;
+        let mut now_stalled = 0;
+        for i in 0..2 {
+            if rpm[i] > 0 {
+                seen_turning[i] = true;
+                still_seconds[i] = 0;
+            } else if applied[i] >= STALL_MIN_DUTY {
+                still_seconds[i] += 1;
+            } else {
+                still_seconds[i] = 0;
+            }
+
+            if seen_turning[i] && still_seconds[i] >= STALL_SECONDS {
+                now_stalled |= 1 << i;
+            }
+        }
+
+        if now_stalled != stalled {
+            if now_stalled != 0 {
+                warn!("fans stalled: {:x}", now_stalled);
+                events::push(EventKind::FanStall, now_stalled as i32).await;
+            }
+            stalled = now_stalled;
+        }
+        debug!("fans: {} rpm, {} rpm", rpm[0], rpm[1]);
+    }
+}
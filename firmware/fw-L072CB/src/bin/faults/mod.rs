@@ -0,0 +1,114 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use defmt::*;
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::Mutex;
+use qaxe_core::events::EventKind;
+
+use crate::pwm::{PWMControl, MAX_DUTY, PWM_TARGET};
+use crate::{events, history};
+use crate::{ResetManagerCommand, RESET_MANAGER_SIGNAL};
+
+// fault bits as reported in `QState.faults`
+pub const TEMP1_OVER: u32 = 1 << 0;
+pub const TEMP2_OVER: u32 = 1 << 1;
+pub const MCU_TEMP_OVER: u32 = 1 << 2;
+pub const VDDA_OUT_OF_RANGE: u32 = 1 << 3;
+#[cfg(feature = "rail-sense")]
+pub const VIN_OUT_OF_RANGE: u32 = 1 << 4;
+#[cfg(feature = "rail-sense")]
+pub const V1V2_OUT_OF_RANGE: u32 = 1 << 5;
+pub const SENSOR_TEMP_OVER: u32 = 1 << 6;
+// a temperature sensor failed, `QState.sensors_failed` tells which
+pub const SENSOR_FAILED: u32 = 1 << 7;
+pub const WATCHDOG_RESET: u32 = 1 << 8;
+
+// Raised faults are reported in `QState.faults`, as an event and in the
+// history. What the firmware does about them on its own is set with
+// `QPwmConfig.shutdown_on` and `full_fans_on`, by default nothing.
+
+// faults that switch the ASIC rail off until the host resets the ASICs
+static SHUTDOWN_ON: AtomicU32 = AtomicU32::new(0);
+// faults that switch both fans to full speed until the host sets them again
+static FULL_FANS_ON: AtomicU32 = AtomicU32::new(0);
+
+static FAULTS: Mutex<ThreadModeRawMutex, u32> = Mutex::new(0u32);
+
+/// Set the faults acted on, as bits of `QState.faults`.
+pub fn set_policy(shutdown_on: u32, full_fans_on: u32) {
+    SHUTDOWN_ON.store(shutdown_on, Ordering::Relaxed);
+    FULL_FANS_ON.store(full_fans_on, Ordering::Relaxed);
+}
+
+/// The faults acted on, see [`set_policy`].
+pub fn policy() -> (u32, u32) {
+    (
+        SHUTDOWN_ON.load(Ordering::Relaxed),
+        FULL_FANS_ON.load(Ordering::Relaxed),
+    )
+}
+
+/// Get the currently active fault bits.
+pub async fn active() -> u32 {
+    *FAULTS.lock().await
+}
+
+/// Set or clear a fault bit.
+///
+/// Raised faults are reported, and acted on when set in the policy.
+pub async fn update(fault: u32, active: bool) {
+    let mut faults = FAULTS.lock().await;
+    let previous = *faults;
+
+    if active {
+        *faults |= fault;
+    } else {
+        *faults &= !fault;
+    }
+
+    let current = *faults;
+    let raised = current & !previous;
+    let cleared = previous & !current;
+    drop(faults);
+
+    if current != previous {
+        events::push(EventKind::Fault, current as i32).await;
+    }
+
+    if cleared != 0 {
+        info!("fault cleared: {:x}", cleared);
+    }
+
+    if raised != 0 {
+        history::add_faults(raised).await;
+        error!("fault raised: {:x}", raised);
+    }
+    let (shutdown_on, full_fans_on) = policy();
+    if raised & shutdown_on != 0 {
+        warn!("shutting down the ASICs");
+        RESET_MANAGER_SIGNAL.signal(ResetManagerCommand::Shutdown);
+    }
+    if raised & full_fans_on != 0 {
+        warn!("fans to full speed");
+        PWM_TARGET.signal(PWMControl {
+            pwm1_value: MAX_DUTY,
+            pwm2_value: MAX_DUTY,
+        });
+    }
+}
+
+/// Evaluate a reading against its limits with hysteresis and update the fault bit.
+///
+/// Outside of `[low, high]` raises the fault, it is only cleared again once the
+/// value is back inside the limits by at least `hysteresis`.
+pub async fn check_limits(fault: u32, value: i32, low: i32, high: i32, hysteresis: i32) {
+    let is_active = (active().await & fault) != 0;
+
+    let out_of_range = if is_active {
+        value < low + hysteresis || value > high - hysteresis
+    } else {
+        value < low || value > high
+    };
+
+    update(fault, out_of_range).await;
+}
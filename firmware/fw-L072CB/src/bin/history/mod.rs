@@ -0,0 +1,56 @@
+use defmt::*;
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Duration, Instant, Ticker};
+use qaxe_core::history::{History, Sample};
+
+use crate::{faults, pwm, sensors};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+// 30 minutes per interval
+const INTERVAL_SAMPLES: u32 = 180;
+// the last 24 hours
+const INTERVALS: usize = 48;
+
+pub static HISTORY: Mutex<ThreadModeRawMutex, History<INTERVALS>> = Mutex::new(History::new());
+
+/// Count a loss of PGOOD in the current interval.
+pub async fn add_pgood_loss() {
+    HISTORY.lock().await.add_pgood_loss();
+}
+
+/// Note faults that may be gone again before the next sample.
+pub async fn add_faults(faults: u32) {
+    HISTORY.lock().await.add_faults(faults);
+}
+
+/// Sample the telemetry and summarize it per interval.
+#[embassy_executor::task]
+pub async fn recorder() {
+    let mut ticker = Ticker::every(SAMPLE_INTERVAL);
+    let mut samples = 0;
+
+    loop {
+        ticker.next().await;
+
+        let sample = Sample {
+            temps: [sensors::temperature(0).await, sensors::temperature(1).await],
+            duties: pwm::PWM_STATE.lock().await.applied,
+        };
+        let active = faults::active().await;
+
+        let mut history = HISTORY.lock().await;
+        history.add_sample(sample);
+        history.add_faults(active);
+
+        samples += 1;
+        if samples == INTERVAL_SAMPLES {
+            samples = 0;
+            let interval = history.close(Instant::now().as_millis() as u32);
+            debug!(
+                "history interval {}: {} faults {:x}",
+                interval.seq, interval.samples, interval.faults
+            );
+        }
+    }
+}
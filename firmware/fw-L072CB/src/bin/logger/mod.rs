@@ -0,0 +1,256 @@
+//! defmt global logger writing to RTT and, filtered by level, to the host.
+//!
+//! The frames sent to the host are rzcobs-encoded like the RTT output, they
+//! are decoded with the defmt table of the ELF, e.g. by `defmt-print`.
+
+use core::cell::RefCell;
+use core::ptr::{addr_of, addr_of_mut};
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+use critical_section::RestoreState;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use heapless::Deque;
+
+// log levels as set with `SetLogLevel`, above debug is trace
+pub const LEVEL_OFF: u8 = 0;
+const LEVEL_ERROR: u8 = 1;
+const LEVEL_WARN: u8 = 2;
+const LEVEL_INFO: u8 = 3;
+const LEVEL_DEBUG: u8 = 4;
+
+const RTT_BUFFER_SIZE: usize = 1024;
+const USB_BUFFER_SIZE: usize = 512;
+
+/// Signalled when there is log output for the host.
+pub static LOG_SIGNAL: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+static LEVEL: AtomicU8 = AtomicU8::new(LEVEL_OFF);
+
+// encoded frames for the host and the bytes lost since the last read
+static USB_BUFFER: critical_section::Mutex<RefCell<(Deque<u8, USB_BUFFER_SIZE>, usize)>> =
+    critical_section::Mutex::new(RefCell::new((Deque::new(), 0)));
+
+/// Set the lowest level sent to the host, `LEVEL_OFF` stops sending.
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+    if level == LEVEL_OFF {
+        critical_section::with(|cs| {
+            let mut buffer = USB_BUFFER.borrow_ref_mut(cs);
+            buffer.0.clear();
+            buffer.1 = 0;
+        });
+    }
+}
+
+/// Take the log output for the host, returns its length and how many bytes
+/// were dropped before it because the host didn't keep up.
+pub fn read(buf: &mut [u8]) -> (usize, usize) {
+    critical_section::with(|cs| {
+        let mut buffer = USB_BUFFER.borrow_ref_mut(cs);
+        let mut n = 0;
+        while n < buf.len() {
+            let Some(byte) = buffer.0.pop_front() else {
+                break;
+            };
+            buf[n] = byte;
+            n += 1;
+        }
+        let dropped = core::mem::take(&mut buffer.1);
+        (n, dropped)
+    })
+}
+
+fn push_usb(bytes: &[u8]) {
+    critical_section::with(|cs| {
+        let mut buffer = USB_BUFFER.borrow_ref_mut(cs);
+        for &byte in bytes {
+            if buffer.0.push_back(byte).is_err() {
+                buffer.1 += 1;
+            }
+        }
+    });
+}
+
+// markers of the log strings per level in the defmt table, see defmt.x, the
+// table is ordered from trace to error
+extern "C" {
+    static __DEFMT_MARKER_TRACE_START: u8;
+    static __DEFMT_MARKER_DEBUG_START: u8;
+    static __DEFMT_MARKER_INFO_START: u8;
+    static __DEFMT_MARKER_WARN_START: u8;
+    static __DEFMT_MARKER_ERROR_START: u8;
+    static __DEFMT_MARKER_ERROR_END: u8;
+}
+
+/// Check the string index a frame starts with against the level for the host.
+fn forward(index: u16) -> bool {
+    // the symbols only carry the index, they are never dereferenced
+    let start = unsafe {
+        match LEVEL.load(Ordering::Relaxed) {
+            LEVEL_OFF => return false,
+            LEVEL_ERROR => addr_of!(__DEFMT_MARKER_ERROR_START),
+            LEVEL_WARN => addr_of!(__DEFMT_MARKER_WARN_START),
+            LEVEL_INFO => addr_of!(__DEFMT_MARKER_INFO_START),
+            LEVEL_DEBUG => addr_of!(__DEFMT_MARKER_DEBUG_START),
+            _ => addr_of!(__DEFMT_MARKER_TRACE_START),
+        }
+    } as usize;
+    let end = unsafe { addr_of!(__DEFMT_MARKER_ERROR_END) } as usize;
+
+    (start..end).contains(&(index as usize))
+}
+
+#[repr(C)]
+struct RttChannel {
+    name: *const u8,
+    buffer: *mut u8,
+    size: usize,
+    write: AtomicUsize,
+    read: AtomicUsize,
+    flags: AtomicUsize,
+}
+
+#[repr(C)]
+struct RttHeader {
+    id: [u8; 16],
+    max_up_channels: usize,
+    max_down_channels: usize,
+    up_channel: RttChannel,
+}
+
+// don't block when the probe doesn't read, drop what doesn't fit
+const RTT_MODE_NO_BLOCK_TRIM: usize = 1;
+
+static mut RTT_BUFFER: [u8; RTT_BUFFER_SIZE] = [0; RTT_BUFFER_SIZE];
+
+// found by the probe in RAM by its id
+#[no_mangle]
+static mut _SEGGER_RTT: RttHeader = RttHeader {
+    id: *b"SEGGER RTT\0\0\0\0\0\0",
+    max_up_channels: 1,
+    max_down_channels: 0,
+    up_channel: RttChannel {
+        name: b"defmt\0".as_ptr(),
+        buffer: unsafe { addr_of_mut!(RTT_BUFFER) as *mut u8 },
+        size: RTT_BUFFER_SIZE,
+        write: AtomicUsize::new(0),
+        read: AtomicUsize::new(0),
+        flags: AtomicUsize::new(RTT_MODE_NO_BLOCK_TRIM),
+    },
+};
+
+fn write_rtt(bytes: &[u8]) {
+    // only written with the logger acquired
+    let channel = unsafe { &*addr_of!(_SEGGER_RTT.up_channel) };
+    let read = channel.read.load(Ordering::Relaxed);
+    let mut write = channel.write.load(Ordering::Acquire);
+
+    for &byte in bytes {
+        let next = (write + 1) % RTT_BUFFER_SIZE;
+        if next == read {
+            break;
+        }
+        unsafe { channel.buffer.add(write).write_volatile(byte) };
+        write = next;
+    }
+    channel.write.store(write, Ordering::Release);
+}
+
+/// Decides per frame from its string index whether it goes to the host.
+enum UsbFrame {
+    Header([u8; 2], usize),
+    Forward,
+    Skip,
+}
+
+struct State {
+    rtt: defmt::Encoder,
+    usb: defmt::Encoder,
+    frame: UsbFrame,
+    restore: RestoreState,
+}
+
+static mut STATE: State = State {
+    rtt: defmt::Encoder::new(),
+    usb: defmt::Encoder::new(),
+    frame: UsbFrame::Skip,
+    restore: RestoreState::invalid(),
+};
+
+static TAKEN: AtomicBool = AtomicBool::new(false);
+// a frame logged while another one is written, e.g. by the panic handler
+static NESTED: AtomicUsize = AtomicUsize::new(0);
+
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {
+        let restore = unsafe { critical_section::acquire() };
+        if TAKEN.load(Ordering::Relaxed) {
+            // the outer frame is still being written, this one is lost
+            NESTED.fetch_add(1, Ordering::Relaxed);
+            unsafe { critical_section::release(restore) };
+            return;
+        }
+        TAKEN.store(true, Ordering::Relaxed);
+
+        let state = unsafe { &mut *addr_of_mut!(STATE) };
+        state.restore = restore;
+        state.rtt.start_frame(write_rtt);
+        state.frame = UsbFrame::Header([0; 2], 0);
+    }
+
+    unsafe fn flush() {}
+
+    unsafe fn release() {
+        if NESTED.load(Ordering::Relaxed) > 0 {
+            NESTED.fetch_sub(1, Ordering::Relaxed);
+            return;
+        }
+
+        let state = &mut *addr_of_mut!(STATE);
+        state.rtt.end_frame(write_rtt);
+        if let UsbFrame::Forward = state.frame {
+            state.usb.end_frame(push_usb);
+            LOG_SIGNAL.signal(());
+        }
+
+        TAKEN.store(false, Ordering::Relaxed);
+        critical_section::release(state.restore);
+    }
+
+    unsafe fn write(bytes: &[u8]) {
+        if NESTED.load(Ordering::Relaxed) > 0 {
+            return;
+        }
+
+        let state = &mut *addr_of_mut!(STATE);
+        state.rtt.write(bytes, write_rtt);
+
+        let mut bytes = bytes;
+        // every frame starts with the u16 index of its format string
+        if let UsbFrame::Header(ref mut header, ref mut len) = state.frame {
+            let n = bytes.len().min(2 - *len);
+            header[*len..*len + n].copy_from_slice(&bytes[..n]);
+            *len += n;
+            bytes = &bytes[n..];
+
+            if *len == 2 {
+                let header = *header;
+                state.frame = if forward(u16::from_le_bytes(header)) {
+                    state.usb.start_frame(push_usb);
+                    state.usb.write(&header, push_usb);
+                    UsbFrame::Forward
+                } else {
+                    UsbFrame::Skip
+                };
+            }
+        }
+
+        if let UsbFrame::Forward = state.frame {
+            state.usb.write(bytes, push_usb);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use core::pin::pin;
+
+use defmt::*;
+use embassy_stm32::peripherals::TIM2;
+use embassy_stm32::time::Hertz;
+use embassy_stm32::timer::low_level::OutputPolarity;
+use embassy_stm32::timer::simple_pwm::SimplePwm;
+use embassy_stm32::timer::Channel as PWMChannel;
+use embassy_sync::blocking_mutex::raw::{CriticalSectionRawMutex, ThreadModeRawMutex};
+use embassy_sync::mutex::Mutex;
+use embassy_sync::signal::Signal;
+use embassy_time::Timer;
+use futures::future::{select, Either};
+
+use crate::watchdog::{self, Task};
+
+/// Duty cycles are given in per-mille.
+pub const MAX_DUTY: u16 = 1000;
+
+const MIN_FREQUENCY_HZ: u32 = 10;
+const MAX_FREQUENCY_HZ: u32 = 100_000;
+const MAX_KICK_MS: u32 = 5000;
+const MAX_RAMP_RATE: u32 = 10_000;
+
+const RAMP_STEP_MS: u64 = 50;
+// wake up regularly to check in with the watchdog
+const IDLE_STEP_MS: u64 = 1000;
+
+const CHANNELS: [PWMChannel; 2] = [PWMChannel::Ch1, PWMChannel::Ch2];
+
+pub struct PWMControl {
+    pub pwm1_value: u16,
+    pub pwm2_value: u16,
+}
+
+#[derive(Clone, Copy)]
+pub struct PwmConfig {
+    pub frequency_hz: u32,
+    /// invert the output polarity per channel
+    pub invert: [bool; 2],
+    /// non-zero duties below are raised to this per channel
+    pub min_duty: [u16; 2],
+    /// duty applied for `kick_ms` when a fan starts from standstill below it
+    pub kick_duty: u16,
+    pub kick_ms: u32,
+    /// duty change in per-mille per second, 0 switches immediately
+    pub ramp_rate: u32,
+}
+
+impl Default for PwmConfig {
+    fn default() -> Self {
+        PwmConfig::DEFAULT
+    }
+}
+
+impl PwmConfig {
+    const DEFAULT: PwmConfig = PwmConfig {
+        frequency_hz: 10_000,
+        invert: [false; 2],
+        min_duty: [0; 2],
+        kick_duty: 0,
+        kick_ms: 0,
+        ramp_rate: 0,
+    };
+
+    /// Check the limits, returns the name of the first field out of range.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !(MIN_FREQUENCY_HZ..=MAX_FREQUENCY_HZ).contains(&self.frequency_hz) {
+            return Err("frequency");
+        }
+        if self.min_duty[0] > MAX_DUTY {
+            return Err("min_duty1");
+        }
+        if self.min_duty[1] > MAX_DUTY {
+            return Err("min_duty2");
+        }
+        if self.kick_duty > MAX_DUTY {
+            return Err("kick_duty");
+        }
+        if self.kick_ms > MAX_KICK_MS {
+            return Err("kick_ms");
+        }
+        if self.ramp_rate > MAX_RAMP_RATE {
+            return Err("ramp_rate");
+        }
+        Ok(())
+    }
+}
+
+/// Duties in per-mille, as currently applied and as requested, and the
+/// config they are applied with.
+#[derive(Clone, Copy)]
+pub struct PwmState {
+    pub applied: [u16; 2],
+    pub target: [u16; 2],
+    pub config: PwmConfig,
+}
+
+// latest setpoint and config, a newer value replaces one not yet picked up
+pub static PWM_TARGET: Signal<CriticalSectionRawMutex, PWMControl> = Signal::new();
+pub static PWM_CONFIG: Signal<CriticalSectionRawMutex, PwmConfig> = Signal::new();
+
+pub static PWM_STATE: Mutex<ThreadModeRawMutex, PwmState> = Mutex::new(PwmState {
+    applied: [MAX_DUTY; 2],
+    target: [MAX_DUTY; 2],
+    config: PwmConfig::DEFAULT,
+});
+
+enum PwmEvent {
+    Target(PWMControl),
+    Config(PwmConfig),
+    Step,
+}
+
+/// Wait for a new setpoint, a new config or the next ramp step.
+async fn next_event(ramping: bool) -> PwmEvent {
+    let step = Timer::after_millis(if ramping { RAMP_STEP_MS } else { IDLE_STEP_MS });
+
+    match select(
+        pin!(PWM_TARGET.wait()),
+        pin!(select(pin!(PWM_CONFIG.wait()), pin!(step))),
+    )
+    .await
+    {
+        Either::Left((target, _)) => PwmEvent::Target(target),
+        Either::Right((Either::Left((config, _)), _)) => PwmEvent::Config(config),
+        Either::Right((Either::Right(_), _)) => PwmEvent::Step,
+    }
+}
+
+/// Move `applied` towards `target` by at most `step`.
+fn ramp(applied: u16, target: u16, step: u16) -> u16 {
+    if applied < target {
+        applied.saturating_add(step).min(target)
+    } else {
+        applied.saturating_sub(step).max(target)
+    }
+}
+
+fn set_duty(pwm: &mut SimplePwm<'static, TIM2>, channel: PWMChannel, duty: u16) {
+    let max_duty = pwm.get_max_duty() as u32;
+    pwm.set_duty(channel, max_duty * duty as u32 / MAX_DUTY as u32);
+}
+
+fn apply_config(pwm: &mut SimplePwm<'static, TIM2>, config: &PwmConfig) {
+    info!(
+        "pwm config: {}Hz, invert: {}, min duty: {}, kick: {} for {}ms, ramp: {}/1000/s",
+        config.frequency_hz,
+        config.invert,
+        config.min_duty,
+        config.kick_duty,
+        config.kick_ms,
+        config.ramp_rate
+    );
+
+    pwm.set_frequency(Hertz(config.frequency_hz));
+
+    for (channel, invert) in CHANNELS.iter().zip(config.invert) {
+        let polarity = if invert {
+            OutputPolarity::ActiveLow
+        } else {
+            OutputPolarity::ActiveHigh
+        };
+        pwm.set_polarity(*channel, polarity);
+    }
+}
+
+#[embassy_executor::task]
+pub async fn pwm_manager(mut pwm1: SimplePwm<'static, TIM2>) {
+    let mut config = PwmConfig::default();
+    // fans start at full speed
+    let mut applied = [MAX_DUTY; 2];
+    let mut targets = [MAX_DUTY; 2];
+
+    loop {
+        watchdog::check_in(Task::Pwm);
+
+        match next_event(applied != targets).await {
+            PwmEvent::Config(new_config) => {
+                config = new_config;
+                apply_config(&mut pwm1, &config);
+            }
+            PwmEvent::Target(pwm) => {
+                targets = [pwm.pwm1_value, pwm.pwm2_value];
+                for (target, min_duty) in targets.iter_mut().zip(config.min_duty) {
+                    if *target != 0 && *target < min_duty {
+                        *target = min_duty;
+                    }
+                }
+                info!("pwm target: {}/1000, {}/1000", targets[0], targets[1]);
+
+                // kick-start fans that don't spin up at low duty from standstill
+                let kick: [bool; 2] = core::array::from_fn(|i| {
+                    applied[i] == 0 && targets[i] != 0 && targets[i] < config.kick_duty
+                });
+
+                if kick.iter().any(|k| *k) {
+                    for (i, channel) in CHANNELS.iter().enumerate() {
+                        if kick[i] {
+                            info!("pwm{}: kick-start", i);
+                            set_duty(&mut pwm1, *channel, config.kick_duty);
+                            applied[i] = targets[i];
+                        }
+                    }
+                    Timer::after_millis(config.kick_ms as u64).await;
+                }
+            }
+            PwmEvent::Step => {}
+        }
+
+        let step = if config.ramp_rate == 0 {
+            MAX_DUTY
+        } else {
+            (config.ramp_rate * RAMP_STEP_MS as u32 / 1000).max(1) as u16
+        };
+
+        for (i, channel) in CHANNELS.iter().enumerate() {
+            applied[i] = ramp(applied[i], targets[i], step);
+            debug!("pwm{}: {}/1000", i, applied[i]);
+            // also refreshes the duty after a frequency change
+            set_duty(&mut pwm1, *channel, applied[i]);
+        }
+
+        *PWM_STATE.lock().await = PwmState {
+            applied,
+            target: targets,
+            config,
+        };
+    }
+}
@@ -2,55 +2,64 @@
 #![no_main]
 
 use core::option::Option::Some;
-use defmt::{panic, *};
-use defmt_rtt as _; // global logger
+use defmt::*;
 use embassy_executor::Spawner;
+use embassy_stm32::adc::Adc;
 use embassy_stm32::gpio::{Input, Level, Output, OutputType, Pull, Speed};
 use embassy_stm32::i2c;
-use embassy_stm32::i2c::I2c;
 use embassy_stm32::rcc::*;
-use embassy_stm32::time::{khz, Hertz};
+use embassy_stm32::time::khz;
 use embassy_stm32::timer::simple_pwm::{PwmPin, SimplePwm};
 use embassy_stm32::timer::Channel as PWMChannel;
 use embassy_stm32::usart::BufferedUart;
 use embassy_stm32::usb::{Driver, Instance};
-use embassy_stm32::{bind_interrupts, peripherals, usart, usb, Config};
+use embassy_stm32::{adc as stm32_adc, bind_interrupts, peripherals, usart, usb, Config};
 use embassy_sync::blocking_mutex::raw::{CriticalSectionRawMutex, ThreadModeRawMutex};
-use embassy_sync::channel::Channel;
 use embassy_sync::mutex::Mutex;
 use embassy_sync::signal::Signal;
-use embassy_time::Timer;
+use embassy_time::{Delay, Duration, Instant, Timer};
 use embedded_io_async::Read;
 use embassy_stm32::rcc::mux::Clk48sel;
 
+mod adc;
+mod crash;
+mod events;
+mod fans;
+mod faults;
+mod history;
+mod logger;
+mod pwm;
+mod sensors;
 mod uid;
+mod watchdog;
 
+use core::future::pending;
+use core::pin::pin;
 use embassy_stm32::timer::low_level::OutputPolarity;
 use embassy_usb::class::cdc_acm::{CdcAcmClass, State};
 use embassy_usb::driver::EndpointError;
 use embassy_usb::Builder;
 use embedded_io_async::Write;
-use futures::future::join4;
-use panic_probe as _;
-
-extern crate alloc;
-extern crate alloc_cortex_m;
-
-mod protobuf;
-use protobuf::coms::{QControl, QRequest, QResponse, QState};
-use quick_protobuf::{self, MessageWrite};
-
-use alloc::borrow::Cow;
-
-use alloc_cortex_m::CortexMHeap;
-
-#[global_allocator]
-static ALLOCATOR: CortexMHeap = CortexMHeap::empty();
+use futures::future::{join4, select, Either};
+
+use heapless::{String, Vec};
+use pwm::{PWMControl, PwmConfig, PWM_CONFIG, PWM_TARGET};
+use qaxe_core::events::EventKind;
+use qaxe_core::framing::{Deframer, Detector, FrameError, Protocol, MAX_FRAME};
+use qaxe_core::jsonrpc::{self, JsonRpc};
+use qaxe_core::protobuf::coms::{
+    QHistory, QLog, QPwmConfig, QResetInfo, QResponse, QSensor, QSensors, QState,
+};
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core::relay;
+use qaxe_core::rpc::{self, error_response, Device, Error, Errors, MAX_RESPONSE_DATA};
+use qaxe_core::shell::{Output, Shell};
 
 bind_interrupts!(struct Irqs {
     USB => usb::InterruptHandler<peripherals::USB>;
     USART1 => usart::BufferedInterruptHandler<peripherals::USART1>;
     I2C2 => i2c::EventInterruptHandler<peripherals::I2C2>, i2c::ErrorInterruptHandler<peripherals::I2C2>;
+    ADC1_COMP => stm32_adc::InterruptHandler<peripherals::ADC>;
 });
 use embassy_stm32::peripherals::*;
 
@@ -64,28 +73,22 @@ static RESET_MANAGER_SIGNAL: Signal<CriticalSectionRawMutex, ResetManagerCommand
 
 static PGOOD: Mutex<ThreadModeRawMutex, bool> = Mutex::new(false);
 
-static TEMP1: Mutex<ThreadModeRawMutex, u16> = Mutex::new(0u16);
-static TEMP2: Mutex<ThreadModeRawMutex, u16> = Mutex::new(0u16);
-
-struct PWMControl {
-    pwm1_value: u16,
-    pwm2_value: u16,
-}
-
-static PWM_CTRL_CHANNEL: Channel<ThreadModeRawMutex, PWMControl, 1> = Channel::new();
-
 const RX_BUF_SIZE : usize = 256;
 const TX_BUF_SIZE : usize = 256;
 
+// log output per QLog, leaves room for the message around it in a frame
+const LOG_CHUNK: usize = 200;
+
+// a host that is connected but silent for this long has likely hung
+const HOST_TIMEOUT: Duration = Duration::from_secs(30);
+// DTR can't be waited for, it's looked at this often while a host is known
+const DTR_POLL: Duration = Duration::from_millis(200);
+
 #[embassy_executor::main]
 async fn main(spawner: Spawner) {
     info!("Hello World!");
 
-
-    // Initialize the allocator before using it
-    let start = cortex_m_rt::heap_start() as usize;
-    let size = 1024;
-    unsafe { ALLOCATOR.init(start, size) }
+    crash::init().await;
 
     let mut config = Config::default();
     config.rcc.hsi48 = Some(Hsi48Config {
@@ -149,6 +152,9 @@ async fn main(spawner: Spawner) {
     let mut config = usart::Config::default();
     config.baudrate = 115200;
 
+    #[cfg(feature = "follow-baud")]
+    let mut uart_baud = config.baudrate;
+
     let mut tx_buf = [0u8; TX_BUF_SIZE];
     let mut rx_buf = [0u8; RX_BUF_SIZE];
 
@@ -168,6 +174,10 @@ async fn main(spawner: Spawner) {
 
     let reset = Output::new(p.PB13, Level::High, Speed::Low);
 
+    // open-collector tach outputs of the fans
+    let tach1 = Input::new(p.PA6, Pull::Up);
+    let tach2 = Input::new(p.PB6, Pull::Up);
+
     let ch1 = PwmPin::new_ch1(p.PA0, OutputType::PushPull);
     let ch2 = PwmPin::new_ch2(p.PA1, OutputType::PushPull);
     let mut pwm1 = SimplePwm::new(
@@ -186,31 +196,44 @@ async fn main(spawner: Spawner) {
     pwm1.enable(PWMChannel::Ch1);
     pwm1.enable(PWMChannel::Ch2);
 
-    let mut i2c_config = embassy_stm32::i2c::Config::default();
-    i2c_config.scl_pullup = true;
-    i2c_config.sda_pullup = true;
-
-    let i2c = I2c::new(
-        p.I2C2,
-        p.PB10,
-        p.PB11,
-        Irqs,
-        p.DMA1_CH4,
-        p.DMA1_CH5,
-        Hertz(100_000),
-        i2c_config, /*Default::default()*/
-    );
+    let i2c = sensors::create_bus(p.I2C2, p.PB10, p.PB11, p.DMA1_CH4, p.DMA1_CH5);
+
+    let adc = Adc::new(p.ADC, Irqs, &mut Delay);
+
+    #[cfg(feature = "rail-sense")]
+    let rails = adc::RailPins {
+        vin: p.PB0,
+        v1v2: p.PB1,
+    };
+    #[cfg(not(feature = "rail-sense"))]
+    let rails = adc::RailPins;
 
     unwrap!(spawner.spawn(reset_manager(run_1v2, reset, ldo_en)));
     unwrap!(spawner.spawn(power_good_task(pgood_1v2, pgood_led)));
-    unwrap!(spawner.spawn(pwm_manager(pwm1)));
-    unwrap!(spawner.spawn(temp_manager(i2c)));
+    unwrap!(spawner.spawn(pwm::pwm_manager(pwm1)));
+    unwrap!(spawner.spawn(fans::tach_monitor(tach1, tach2)));
+    *sensors::I2C_BUS.lock().await = Some(i2c);
+
+    unwrap!(spawner.spawn(sensors::sensor_manager()));
+    unwrap!(spawner.spawn(adc::adc_manager(adc, rails)));
+    unwrap!(spawner.spawn(history::recorder()));
+    unwrap!(spawner.spawn(watchdog::supervisor(p.IWDG)));
+
+    // the board starts with the ASICs off and the fans at full speed, after a
+    // hang they stay that way and the host is told why
+    if crash::RESET_INFO.lock().await.cause & crash::RESET_IWDG != 0 {
+        warn!("restarted by watchdog");
+        faults::update(faults::WATCHDOG_RESET, true).await;
+    }
 
     let protobuf_rpc_fut = async {
         loop {
+            watchdog::idle(watchdog::Task::Rpc);
             class_usb_ctrl.wait_connection().await;
             info!("Connected");
             let _ = json_rpc(&mut class_usb_ctrl).await;
+            // nobody reads the log output anymore
+            logger::set_level(logger::LEVEL_OFF);
             info!("Disconnected");
         }
     };
@@ -230,6 +253,30 @@ async fn main(spawner: Spawner) {
                     }
                 };
 
+                // follow the baud rate the host sets on the interface like a
+                // USB-serial bridge, e.g. after switching the ASICs to 1 Mbaud.
+                // The packet goes out at the rate set when it was sent.
+                #[cfg(feature = "follow-baud")]
+                {
+                    let baud = receiver.line_coding().data_rate();
+                    if baud != uart_baud {
+                        match relay::valid_baud(baud) {
+                            Some(baud) => {
+                                // commands at the old rate have to go out first
+                                let _ = tx_ctrl.flush().await;
+                                let mut config = usart::Config::default();
+                                config.baudrate = baud;
+                                match tx_ctrl.set_config(&config) {
+                                    Ok(()) => info!("ASIC baud rate: {}", baud),
+                                    Err(e) => error!("Error setting baud rate: {:?}", e),
+                                }
+                            }
+                            None => warn!("unsupported baud rate: {}", baud),
+                        }
+                        uart_baud = baud;
+                    }
+                }
+
                 if usb_read == 0 {
                     continue; // No data read, continue the loop
                 }
@@ -251,9 +298,8 @@ async fn main(spawner: Spawner) {
             info!("Connected relay sender");
 
             let mut toggle = 0;
-            let mut num_bytes = 0;
-            let mut received = [0u8;11];
-            let preample = [0xaa, 0x55];
+            // drops bytes until the next 0xaa 0x55 preamble
+            let mut sync = relay::ResponseSync::new();
             loop {
                 let mut byte = [0u8;1];
                 match rx_ctrl.read_exact(&mut byte).await {
@@ -263,25 +309,9 @@ async fn main(spawner: Spawner) {
                     }
                 };
 
-                received[num_bytes] = byte[0];
-
-                // try to sync on serial data
-                match num_bytes {
-                    0 | 1 => // wait for 0xaa 0x55
-                        if received[num_bytes] != preample[num_bytes] {
-                            debug!("unexpected start of serial data, trying to resync ...");
-                            num_bytes = 0;
-                            continue;
-                        },
-                    _ => {},
-                };
-
-                num_bytes += 1;
-
-                if num_bytes != 11 {
+                let Some(received) = sync.push(byte[0]) else {
                     continue;
-                }
-                num_bytes = 0;
+                };
 
                 // toggle led with each response received
                 toggle = 1 - toggle;
@@ -312,7 +342,10 @@ async fn main(spawner: Spawner) {
 #[embassy_executor::task]
 async fn power_good_task(pgood_1v2: Input<'static>, mut pgood_led: Output<'static>) {
     loop {
+        watchdog::check_in(watchdog::Task::PowerGood);
+
         let mut pgood_state = PGOOD.lock().await;
+        let previous = *pgood_state;
         if pgood_1v2.is_high() {
             *pgood_state = true;
             pgood_led.set_low();
@@ -320,7 +353,15 @@ async fn power_good_task(pgood_1v2: Input<'static>, mut pgood_led: Output<'stati
             *pgood_state = false;
             pgood_led.set_high();
         }
+        let current = *pgood_state;
         drop(pgood_state);
+
+        if current != previous {
+            events::push(EventKind::Power, current as i32).await;
+            if previous {
+                history::add_pgood_loss().await;
+            }
+        }
         Timer::after_millis(500).await;
     }
 }
@@ -362,240 +403,586 @@ async fn reset_manager(mut run_1v2: Output<'static>, mut reset: Output<'static>,
     }
 }
 
-#[embassy_executor::task]
-async fn pwm_manager(mut pwm1: SimplePwm<'static, TIM2>) {
-    loop {
-        let pwm = PWM_CTRL_CHANNEL.receive().await;
-        let max_duty = pwm1.get_max_duty() as u32;
-        for i in 0..2 {
-            let (channel, value) = match i {
-                0 => (PWMChannel::Ch1, pwm.pwm1_value),
-                1 => (PWMChannel::Ch2, pwm.pwm2_value),
-                _ => (PWMChannel::Ch3, 0),
-            };
-            let duty = max_duty * value as u32 / 100;
-            info!("pwm{}: {}, max: {}", i, duty, max_duty);
-            pwm1.set_duty(
-                channel,
-                if duty <= max_duty {
-                    duty
-                } else {
-                    max_duty
-                },
-            );
-        }
-
-        Timer::after_millis(500).await;
-    }
-}
 struct Disconnected {}
 
 impl From<EndpointError> for Disconnected {
     fn from(val: EndpointError) -> Self {
+        if let EndpointError::BufferOverflow = val {
+            // only reads with a short buffer overflow, restart the session
+            error!("usb buffer overflow");
+        }
+        Disconnected {}
+    }
+}
+
+impl From<sensors::AccessError> for Error {
+    fn from(val: sensors::AccessError) -> Self {
         match val {
-            EndpointError::BufferOverflow => panic!("Buffer overflow"),
-            EndpointError::Disabled => Disconnected {},
+            sensors::AccessError::Denied => Error::field(Errors::AccessDenied, "address"),
+            sensors::AccessError::Busy => Errors::Busy.into(),
+            sensors::AccessError::Bus(e) => {
+                error!("i2c error: {:?}", e);
+                Errors::I2cError.into()
+            }
         }
     }
 }
 
-enum Errors {
-    None = 0,
-    InvalidCommand = 1,
-    ErrorDeserializingRequest = 2,
-    ErrorSerializingResponse = 3,
-    ErrorDeserializingRequestData = 4,
-    ErrorSerializingResponseData = 5,
+/// The hardware side of the control protocol, one per connection.
+#[derive(Default)]
+struct Board {
+    /// `EventKind` mask the host subscribed to
+    events: u32,
+    /// interval of the status stream, if streaming
+    state_interval: Option<Duration>,
+    /// when the next status is streamed
+    next_state: Option<Instant>,
+    /// copy of the panic message for `reset_info` to borrow
+    panic: String<crash::MAX_MESSAGE>,
 }
 
-impl Errors {
-    fn to_string(error: &Errors) -> &'static str {
-        match error {
-            Errors::InvalidCommand => "invalid command",
-            Errors::ErrorDeserializingRequest => "error deserializing request",
-            Errors::ErrorSerializingResponse => "error serializing response",
-            Errors::ErrorDeserializingRequestData => "error deserializing request data",
-            Errors::ErrorSerializingResponseData => "error serializing response data",
-            _ => "unknown error",
+impl Device for Board {
+    fn set_pwm(&mut self, pwm1: u16, pwm2: u16) {
+        info!("ctrl command with pwm1: {}/1000, pwm2: {}/1000", pwm1, pwm2);
+        PWM_TARGET.signal(PWMControl {
+            pwm1_value: pwm1,
+            pwm2_value: pwm2,
+        });
+    }
+
+    fn set_pwm_config(&mut self, cmd: &QPwmConfig) -> Result<(), Error> {
+        fn field<T: TryFrom<i32>>(value: i32, name: &'static str) -> Result<T, Error> {
+            T::try_from(value).map_err(|_| Error::field(Errors::OutOfRange, name))
         }
+
+        let config = PwmConfig {
+            frequency_hz: field(cmd.frequency, "frequency")?,
+            invert: [cmd.invert1, cmd.invert2],
+            min_duty: [
+                field(cmd.min_duty1, "min_duty1")?,
+                field(cmd.min_duty2, "min_duty2")?,
+            ],
+            kick_duty: field(cmd.kick_duty, "kick_duty")?,
+            kick_ms: field(cmd.kick_ms, "kick_ms")?,
+            ramp_rate: field(cmd.ramp_rate, "ramp_rate")?,
+        };
+        config
+            .validate()
+            .map_err(|name| Error::field(Errors::OutOfRange, name))?;
+        let shutdown_on = field(cmd.shutdown_on, "shutdown_on")?;
+        let full_fans_on = field(cmd.full_fans_on, "full_fans_on")?;
+
+        PWM_CONFIG.signal(config);
+        faults::set_policy(shutdown_on, full_fans_on);
+        Ok(())
+    }
+
+    async fn pwm_config(&mut self) -> Result<QPwmConfig, Error> {
+        let config = pwm::PWM_STATE.lock().await.config;
+        let (shutdown_on, full_fans_on) = faults::policy();
+        Ok(QPwmConfig {
+            frequency: config.frequency_hz as i32,
+            invert1: config.invert[0],
+            invert2: config.invert[1],
+            min_duty1: config.min_duty[0] as i32,
+            min_duty2: config.min_duty[1] as i32,
+            kick_duty: config.kick_duty as i32,
+            kick_ms: config.kick_ms as i32,
+            ramp_rate: config.ramp_rate as i32,
+            shutdown_on: shutdown_on as i32,
+            full_fans_on: full_fans_on as i32,
+        })
     }
-}
-enum Commands {
-    Nop = 0,
-    Control = 1,
-    Status = 2,
-    Reset = 3,
-    Shutdown = 4,
-}
 
-impl Commands {
-    fn from_i32(value: i32) -> Option<Commands> {
-        match value {
-            0 => Some(Commands::Nop),
-            1 => Some(Commands::Control),
-            2 => Some(Commands::Status),
-            3 => Some(Commands::Reset),
-            4 => Some(Commands::Shutdown),
-            _ => None,
+    fn reset(&mut self) {
+        RESET_MANAGER_SIGNAL.signal(ResetManagerCommand::Reset);
+    }
+
+    fn shutdown(&mut self) {
+        RESET_MANAGER_SIGNAL.signal(ResetManagerCommand::Shutdown);
+    }
+
+    async fn status(&mut self) -> QState {
+        debug!("status");
+        let timestamp = Instant::now();
+        // get current power state
+        let pgood_state = *PGOOD.lock().await;
+
+        let readings = *adc::ADC_READINGS.lock().await;
+        let pwm_state = *pwm::PWM_STATE.lock().await;
+        let fan_rpm = *fans::FAN_RPM.lock().await;
+
+        QState {
+            pgood_1v2: pgood_state as i32,
+            temp1: sensors::temperature(0).await,
+            temp2: sensors::temperature(1).await,
+            vin_mv: readings.vin_mv as i32,
+            v1v2_mv: readings.v1v2_mv as i32,
+            vdda_mv: readings.vdda_mv as i32,
+            mcu_temp: readings.mcu_temp,
+            faults: faults::active().await as i32,
+            sensors_failed: sensors::failed().await as i32,
+            pwm1_applied: pwm_state.applied[0] as i32,
+            pwm2_applied: pwm_state.applied[1] as i32,
+            pwm1_target: pwm_state.target[0] as i32,
+            pwm2_target: pwm_state.target[1] as i32,
+            fan1_rpm: fan_rpm[0] as i32,
+            fan2_rpm: fan_rpm[1] as i32,
+            timestamp_us: timestamp.as_micros(),
         }
     }
-}
 
-impl QResponse<'_> {
-    fn default() -> QResponse<'static> {
-        QResponse {
-            id: 0,
-            error: 0,
-            data: Cow::Borrowed(&[0u8]),
+    async fn sensors(&mut self) -> QSensors {
+        info!("sensors");
+        let mut state = QSensors::default();
+        for sensor in sensors::SENSORS.lock().await.iter() {
+            state.sensors.push(QSensor {
+                address: sensor.config.address as i32,
+                kind: sensor.config.kind as i32,
+                temp: sensor.reading.temp,
+                voltage_mv: sensor.reading.voltage_mv,
+                current_ma: sensor.reading.current_ma,
+                power_mw: sensor.reading.power_mw,
+                health: sensor.health as i32,
+                errors: sensor.errors as i32,
+            });
         }
+        state
     }
-}
 
-// The response_bytes should be a mutable slice of u8, not a slice of a mutable slice.
-async fn process_request<'a>(
-    request: &QRequest<'_>,
-    response: &mut QResponse<'_>,
-) -> Result<usize, Errors> {
-    let mut response_data = [0u8; 32];
-    let mut response_len = 0;
-    let error = Errors::None as i32;
+    async fn i2c_scan(&mut self) -> Result<Vec<u8, 112>, Error> {
+        let addresses = sensors::scan().await?;
+        info!("i2c scan: {:x}", &addresses[..]);
+        Ok(addresses)
+    }
 
-    let op = Commands::from_i32(request.op);
-    if op.is_none() {
-        return Err(Errors::InvalidCommand);
+    async fn i2c_read(&mut self, address: u8, reg: u8, data: &mut [u8]) -> Result<(), Error> {
+        sensors::read_register(address, reg, data).await?;
+        info!("i2c read {:x}/{:x}: {:x}", address, reg, data);
+        Ok(())
     }
 
-    match op.unwrap() {
-        Commands::Nop => {
-            // nop
+    async fn i2c_write(&mut self, address: u8, reg: u8, data: &[u8]) -> Result<(), Error> {
+        info!("i2c write {:x}/{:x}: {:x}", address, reg, data);
+        sensors::write_register(address, reg, data).await?;
+        Ok(())
+    }
+
+    async fn set_bringup_mode(&mut self, key: u32) -> Result<bool, Error> {
+        Ok(sensors::set_bringup_mode(key).await)
+    }
+
+    async fn reset_info(&mut self) -> QResetInfo<'_> {
+        let info = crash::RESET_INFO.lock().await;
+        self.panic.clone_from(&info.panic);
+        QResetInfo {
+            cause: info.cause as i32,
+            boots: info.boots as i32,
+            panic: &self.panic,
         }
-        Commands::Control => {
-            let cmd: QControl = quick_protobuf::deserialize_from_slice(&request.data)
-                .map_err(|_| Errors::ErrorDeserializingRequestData)?;
+    }
+
+    fn subscribe(&mut self, mask: u32, state_interval_ms: u32) -> Result<(), Error> {
+        info!(
+            "subscribe to events {:x}, status every {} ms",
+            mask, state_interval_ms
+        );
+        self.events = mask;
+        events::EVENT_SIGNAL.signal(());
+
+        self.state_interval = match state_interval_ms {
+            0 => None,
+            ms => Some(Duration::from_millis(ms as u64)),
+        };
+        // the first sample goes out right away
+        self.next_state = self.state_interval.map(|_| Instant::now());
+        Ok(())
+    }
+
+    fn set_log_level(&mut self, level: u8) -> Result<(), Error> {
+        info!("log level for the host: {}", level);
+        logger::set_level(level);
+        Ok(())
+    }
+
+    async fn history(
+        &mut self,
+        start: u32,
+        count: usize,
+        max_size: usize,
+    ) -> Result<QHistory, Error> {
+        let history = history::HISTORY.lock().await;
+        Ok(history.page(start, count, max_size))
+    }
+}
 
-            info!(
-                "received ctrl command with parameters state_1v2: {}, pwm1: {}, pwm2: {}",
-                cmd.state_1v2, cmd.pwm1, cmd.pwm2
+/// Decode and process one framed request.
+async fn handle_frame<'b>(
+    board: &'b mut Board,
+    frame: &[u8],
+    response_data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> QResponse<'b> {
+    let mut response = QResponse::default();
+
+    let request = match deserialize_from_slice(frame) {
+        Ok(req) => req,
+        Err(_) => {
+            error!("{}", Errors::to_string(&Errors::ErrorDeserializingRequest));
+            return error_response(
+                rpc::request_id(frame),
+                Errors::ErrorDeserializingRequest.into(),
             );
+        }
+    };
+
+    if let Err(e) = rpc::process_request(board, &request, &mut response, response_data).await {
+        error!("{}: {}", Errors::to_string(&e.code), e.field);
+        response = error_response(request.id, e);
+    }
+    response
+}
 
-            PWM_CTRL_CHANNEL
-                .send(PWMControl {
-                    pwm1_value: cmd.pwm1 as u16,
-                    pwm2_value: cmd.pwm2 as u16,
-                })
-                .await;
+/// Send bytes split into as many packets as needed.
+async fn write_bytes<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    bytes: &[u8],
+) -> Result<(), Disconnected> {
+    // the host reads at its own pace, one that stopped reading mustn't get
+    // the board reset by the watchdog
+    watchdog::idle(watchdog::Task::Rpc);
+    let result = async {
+        let max_packet_size = class.max_packet_size() as usize;
+        for packet in bytes.chunks(max_packet_size) {
+            class.write_packet(packet).await?;
         }
-        Commands::Status => {
-            info!("status");
-            // get current power state
-            let pgood_state = PGOOD.lock().await;
-
-            let temp1 = TEMP1.lock().await;
-            let temp1_data = *temp1;
-
-            let temp2 = TEMP2.lock().await;
-            let temp2_data = *temp2;
-
-            let state = QState {
-                pgood_1v2: *pgood_state as i32,
-                temp1: temp1_data as i32,
-                temp2: temp2_data as i32,
-            };
-            drop(pgood_state);
-            drop(temp1);
-            drop(temp2);
-
-            response_len = state.get_size() + 1 /* varint */;
-            debug!("response-len: {}", response_len);
-            quick_protobuf::serialize_into_slice(&state, &mut response_data[..])
-                .map_err(|_| Errors::ErrorSerializingResponseData)?;
+        // a full last packet doesn't end the transfer on the host side
+        if bytes.len() % max_packet_size == 0 {
+            class.write_packet(&[]).await?;
         }
-        Commands::Reset => RESET_MANAGER_SIGNAL.signal(ResetManagerCommand::Reset),
-        Commands::Shutdown => RESET_MANAGER_SIGNAL.signal(ResetManagerCommand::Shutdown),
-    };
+        Ok(())
+    }
+    .await;
+    watchdog::check_in(watchdog::Task::Rpc);
+    result
+}
 
-    response.id = request.id;
-    response.error = error;
-    response.data = Cow::Owned(response_data[..response_len].to_vec());
-    debug!(
-        "response.id: {}, response.error:{}, response.data: {:?}",
-        response.id,
-        response.error,
-        response_data[..response_len]
-    );
-    Ok(response_len)
+/// Send a response frame, split into as many packets as needed.
+async fn write_response<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    response: &QResponse<'_>,
+) -> Result<(), Disconnected> {
+    let mut response_bytes = [0u8; MAX_FRAME];
+
+    let Ok(serialized_len) = serialize_into_slice(response, &mut response_bytes) else {
+        error!("{}", Errors::to_string(&Errors::ErrorSerializingResponse));
+        return Ok(());
+    };
+    write_bytes(class, &response_bytes[..serialized_len]).await
 }
 
-async fn json_rpc<'d, T: Instance + 'd>(
+/// Send the events the host subscribed to, starting with the backlog.
+async fn send_events<'d, T: Instance + 'd>(
     class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    board: &Board,
 ) -> Result<(), Disconnected> {
-    let mut request_bytes = [0u8; 64];
-    let mut response_bytes = [0u8; 64];
+    // without a subscription the backlog is kept for the next host
+    if board.events == 0 {
+        return Ok(());
+    }
 
     loop {
-        let n = class.read_packet(&mut request_bytes).await?;
+        let event = events::EVENT_LOG.lock().await.next_unsent(board.events);
+        let Some(event) = event else {
+            return Ok(());
+        };
+        write_response(class, &event.to_response()).await?;
+        events::EVENT_LOG.lock().await.sent(&event);
+    }
+}
 
-        let mut response = QResponse::default();
+/// What woke up the control channel.
+enum Wake {
+    Packet(Result<usize, EndpointError>),
+    /// new events or log output
+    Event,
+    /// the host timeout or the next status sample
+    Deadline,
+}
 
-        let request = match quick_protobuf::deserialize_from_slice(&request_bytes[..n]) {
-            Ok(req) => Some(req),
-            Err(_) => {
-                error!("{}", Errors::to_string(&Errors::ErrorDeserializingRequest));
-                response = QResponse::default();
-                response.error = Errors::ErrorDeserializingRequest as i32;
-                None
-            }
+/// Send the log output for the host.
+async fn send_logs<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+) -> Result<(), Disconnected> {
+    let mut data = [0u8; LOG_CHUNK];
+    loop {
+        let (n, dropped) = logger::read(&mut data);
+        if n == 0 && dropped == 0 {
+            return Ok(());
+        }
+
+        let response = QResponse {
+            log: Some(QLog {
+                data: &data[..n],
+                dropped: dropped as i32,
+            }),
+            ..Default::default()
         };
+        write_response(class, &response).await?;
+    }
+}
+
+/// Stream a status snapshot when it is due.
+async fn send_state<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    board: &mut Board,
+) -> Result<(), Disconnected> {
+    let (Some(at), Some(interval)) = (board.next_state, board.state_interval) else {
+        return Ok(());
+    };
+    let now = Instant::now();
+    if at > now {
+        return Ok(());
+    }
+
+    // a slow host skips samples instead of getting a burst of them
+    board.next_state = Some((at + interval).max(now));
+    let state = board.status().await;
+    write_response(class, &rpc::state_response(state)).await
+}
+
+/// Wait for a packet from the host, new events or logs or the next deadline.
+async fn next_wake<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    packet: &mut [u8],
+    deadline: Option<Instant>,
+) -> Wake {
+    let timer = async {
+        match deadline {
+            Some(at) => Timer::at(at).await,
+            None => pending().await,
+        }
+    };
+
+    match select(
+        pin!(class.read_packet(packet)),
+        pin!(select(
+            pin!(select(
+                pin!(events::EVENT_SIGNAL.wait()),
+                pin!(logger::LOG_SIGNAL.wait())
+            )),
+            pin!(timer)
+        )),
+    )
+    .await
+    {
+        Either::Left((result, _)) => Wake::Packet(result),
+        Either::Right((Either::Left(_), _)) => Wake::Event,
+        Either::Right((Either::Right(_), _)) => Wake::Deadline,
+    }
+}
+
+/// The protocol a host speaks and its input collected so far.
+#[derive(Default)]
+struct Session {
+    detector: Detector,
+    protocol: Option<Protocol>,
+    // requests are varint length-delimited and may span several packets
+    deframer: Deframer<MAX_FRAME>,
+    shell: Shell,
+    json: JsonRpc,
+}
+
+impl Session {
+    /// Start over, the next bytes are told apart again.
+    fn reset(&mut self) {
+        self.detector.clear();
+        self.protocol = None;
+        self.deframer.clear();
+        self.shell.clear();
+        self.json.clear();
+    }
+
+    /// Whether the host is in the middle of a line.
+    fn pending(&self) -> bool {
+        match self.protocol {
+            Some(Protocol::Shell) => self.shell.pending(),
+            Some(Protocol::JsonRpc) => self.json.pending(),
+            _ => false,
+        }
+    }
+}
 
-        // if request is some then we can process the request
-        if request.is_some() {
-            if let Err(e) = process_request(&request.unwrap(), &mut response).await {
-                error!("{}", Errors::to_string(&e));
-                response = QResponse::default();
-                response.error = e as i32;
+/// Hand received bytes to the protocol, returns how many were taken. Fewer
+/// than given are only taken when the shell was left.
+async fn feed<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    board: &mut Board,
+    session: &mut Session,
+    input: &[u8],
+) -> Result<usize, Disconnected> {
+    match session.protocol {
+        Some(Protocol::Protobuf) => {
+            let mut data = input;
+            while !data.is_empty() {
+                let used = session.deframer.push(data);
+                data = &data[used..];
+
+                while let Some(frame) = session.deframer.next_frame() {
+                    let mut response_data = [0u8; MAX_RESPONSE_DATA];
+                    let response = match frame {
+                        Ok(frame) => handle_frame(board, frame, &mut response_data).await,
+                        Err(e) => {
+                            error!("invalid frame: {}", e);
+                            let code = match e {
+                                FrameError::Oversized => Errors::OversizedFrame,
+                                FrameError::InvalidLength => Errors::ErrorDeserializingRequest,
+                            };
+                            error_response(0, code.into())
+                        }
+                    };
+
+                    write_response(class, &response).await?;
+                }
             }
+            Ok(input.len())
         }
+        Some(Protocol::Shell) => {
+            let mut out = Output::new();
+            for (i, &byte) in input.iter().enumerate() {
+                if !session.shell.push(byte, &mut out) {
+                    continue;
+                }
+                // the echo goes out before the command runs
+                write_bytes(class, out.as_bytes()).await?;
+                out.clear();
+                if !session.shell.run(board, &mut out).await {
+                    info!("shell left");
+                    session.protocol = None;
+                    return Ok(i + 1);
+                }
+                write_bytes(class, out.as_bytes()).await?;
+                out.clear();
+            }
+            if !out.is_empty() {
+                write_bytes(class, out.as_bytes()).await?;
+            }
+            Ok(input.len())
+        }
+        Some(Protocol::JsonRpc) => {
+            for &byte in input {
+                if session.json.push(byte) {
+                    let mut out = jsonrpc::Output::new();
+                    session.json.run(board, &mut out).await;
+                    // notifications have no response
+                    if !out.is_empty() {
+                        write_bytes(class, &out).await?;
+                    }
+                }
+            }
+            Ok(input.len())
+        }
+        None => Ok(0),
+    }
+}
 
-        let serialized_len = response.get_size() + 1 /* varint */;
-        if quick_protobuf::serialize_into_slice(&response, &mut response_bytes).is_err() {
-            error!("{}", Errors::to_string(&Errors::ErrorSerializingResponse));
+/// Handle a packet from the host in the protocol it speaks, telling it from
+/// the first bytes.
+async fn receive<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+    board: &mut Board,
+    session: &mut Session,
+    mut data: &[u8],
+) -> Result<(), Disconnected> {
+    while !data.is_empty() {
+        if session.protocol.is_some() {
+            let used = feed(class, board, session, data).await?;
+            data = &data[used..];
             continue;
         }
 
-        class
-            .write_packet(&response_bytes[..serialized_len])
-            .await?;
+        if let Some((protocol, start)) = session.detector.push(data[0]) {
+            info!("host speaks {}", protocol);
+            session.protocol = Some(protocol);
+            feed(class, board, session, &start).await?;
+        }
+        data = &data[1..];
     }
+    Ok(())
 }
 
-#[embassy_executor::task]
-async fn temp_manager(mut i2c: I2c<'static, I2C2, DMA1_CH4, DMA1_CH5>) {
-    loop {
-        Timer::after_millis(5000).await;
+/// Serve the host on the control port, in JSON-RPC, protobuf or the shell.
+async fn json_rpc<'d, T: Instance + 'd>(
+    class: &mut CdcAcmClass<'d, Driver<'d, T>>,
+) -> Result<(), Disconnected> {
+    let mut packet = [0u8; 64];
+    let mut session = Session::default();
+    let mut board = Board::default();
 
-        for i in 0..2 {
-            let mut data = [0u8; 2];
-            if let Err(e) = i2c.read(0x48 + i, &mut data).await {
-                error!("i2c error: {:?}", e);
-                continue;
-            }
+    let mut last_request = Instant::now();
+    let mut host_timed_out = false;
+    let mut dtr = class.dtr();
 
-            let mut temp_data = ((data[0] as u16) << 4) | ((data[1] as u16) >> 4);
+    loop {
+        send_events(class, &board).await?;
+        send_state(class, &mut board).await?;
+        send_logs(class).await?;
+
+        // waiting for the host can take forever, processing a request can't
+        watchdog::idle(watchdog::Task::Rpc);
+        let host_timeout = (!host_timed_out).then(|| last_request + HOST_TIMEOUT);
+        let dtr_poll = (dtr && session.protocol.is_some()).then(|| Instant::now() + DTR_POLL);
+        let deadline = [host_timeout, board.next_state, dtr_poll]
+            .into_iter()
+            .flatten()
+            .min();
+        let wake = next_wake(class, &mut packet, deadline).await;
+        watchdog::check_in(watchdog::Task::Rpc);
+
+        // a terminal or tool closed without leaving its protocol, e.g. the
+        // shell without `exit`, would keep the next host from being understood
+        let was_dtr = core::mem::replace(&mut dtr, class.dtr());
+        if was_dtr && !dtr && session.protocol.is_some() {
+            info!("DTR dropped, protocol reset");
+            session.reset();
+        }
 
-            if temp_data > 2047 {
-                temp_data -= 4096
+        let n = match wake {
+            Wake::Packet(Ok(n)) => n,
+            Wake::Packet(Err(EndpointError::BufferOverflow)) => {
+                // the rest of the packet is lost and with it the frame it belonged to
+                warn!("control packet too large");
+                session.deframer.clear();
+                if matches!(session.protocol, None | Some(Protocol::Protobuf)) {
+                    write_response(class, &error_response(0, Errors::OversizedFrame.into()))
+                        .await?;
+                }
+                continue;
             }
-
-            info!("read temp{}: {}", i + 1, temp_data);
-
-            if i == 0 {
-                let mut temp1 = TEMP1.lock().await;
-                *temp1 = temp_data;
-            } else {
-                let mut temp2 = TEMP2.lock().await;
-                *temp2 = temp_data;
+            Wake::Packet(Err(e)) => return Err(e.into()),
+            Wake::Event => continue,
+            Wake::Deadline => {
+                if !host_timeout.is_some_and(|at| at <= Instant::now()) {
+                    // time for the next status sample or to look at DTR
+                    continue;
+                }
+                host_timed_out = true;
+                let protocol = session.protocol;
+                if !session.pending() {
+                    session.reset();
+                }
+                // a technician at a terminal is allowed to think
+                if protocol == Some(Protocol::Shell) {
+                    continue;
+                }
+                warn!("no request from the host for {} s", HOST_TIMEOUT.as_secs());
+                let silent_ms = last_request.elapsed().as_millis() as i32;
+                events::push(EventKind::HostTimeout, silent_ms).await;
+                continue;
             }
-        }
+        };
+        last_request = Instant::now();
+        host_timed_out = false;
 
+        receive(class, &mut board, &mut session, &packet[..n]).await?;
     }
 }
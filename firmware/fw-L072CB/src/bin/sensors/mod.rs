@@ -0,0 +1,592 @@
+use core::future::Future;
+
+use defmt::*;
+use embassy_stm32::gpio::{Flex, Pull, Speed};
+use embassy_stm32::i2c::{self, Error, I2c};
+use embassy_stm32::peripherals::{DMA1_CH4, DMA1_CH5, I2C2, PB10, PB11};
+use embassy_stm32::time::Hertz;
+use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
+use embassy_sync::mutex::{Mutex, MutexGuard};
+use embassy_time::{with_timeout, Duration, Timer};
+use heapless::Vec;
+use qaxe_core::rpc::MAX_TRANSFER;
+
+use crate::watchdog::{self, Task};
+use crate::{faults, Irqs};
+
+pub const MAX_SENSORS: usize = 4;
+
+// a transfer taking longer than this is considered a hung bus
+const I2C_TIMEOUT: Duration = Duration::from_millis(100);
+
+// consecutive failed reads until a sensor is considered failed
+const MAX_FAILURES: u32 = 3;
+
+// unlocks raw writes to the sensors' own addresses
+pub const BRINGUP_KEY: u32 = 0x51415845; // "QAXE"
+
+// sensors that are always polled, temp1 and temp2 are the first two with a
+// temperature, devices found by bus scan at boot are added after them
+const SENSOR_CONFIG: &[SensorConfig] = &[
+    SensorConfig {
+        kind: SensorKind::Tmp,
+        address: 0x48,
+        shunt_mohm: 0,
+    },
+    SensorConfig {
+        kind: SensorKind::Tmp,
+        address: 0x49,
+        shunt_mohm: 0,
+    },
+];
+
+// shunt for power monitors found by bus scan
+const DEFAULT_SHUNT_MOHM: i32 = 10;
+
+// temperature alarm limits in 1/16°C
+const TEMP_MIN: i32 = -40 * 16;
+const TEMP_MAX: i32 = 85 * 16;
+const TEMP_HYST: i32 = 5 * 16;
+
+// INA2xx registers
+const INA_SHUNT_VOLTAGE: u8 = 0x01;
+const INA_BUS_VOLTAGE: u8 = 0x02;
+const INA226_MANUFACTURER_ID: u8 = 0xfe;
+const INA226_TI_ID: u16 = 0x5449;
+
+// PMBus commands
+const PMBUS_VOUT_MODE: u8 = 0x20;
+const PMBUS_READ_VOUT: u8 = 0x8b;
+const PMBUS_READ_IOUT: u8 = 0x8c;
+const PMBUS_READ_TEMPERATURE_1: u8 = 0x8d;
+const PMBUS_READ_POUT: u8 = 0x96;
+const PMBUS_REVISION: u8 = 0x98;
+
+// INA219 has no ID register, it can only be given in SENSOR_CONFIG
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Format)]
+pub enum SensorKind {
+    Tmp = 1,
+    Ina219 = 2,
+    Ina226 = 3,
+    Pmbus = 4,
+}
+
+#[derive(Clone, Copy)]
+pub struct SensorConfig {
+    pub kind: SensorKind,
+    pub address: u8,
+    /// shunt of the INA2xx, sensors without one are left out
+    pub shunt_mohm: i32,
+}
+
+/// Latest values of a sensor, fields a sensor doesn't provide stay 0.
+#[derive(Clone, Copy, Default)]
+pub struct SensorReading {
+    /// temperature in 1/16°C
+    pub temp: i32,
+    pub voltage_mv: i32,
+    pub current_ma: i32,
+    pub power_mw: i32,
+}
+
+#[derive(Clone, Copy, PartialEq, Format)]
+pub enum SensorHealth {
+    Unknown = 0,
+    Ok = 1,
+    Degraded = 2,
+    Failed = 3,
+}
+
+#[derive(Clone, Copy)]
+pub struct Sensor {
+    pub config: SensorConfig,
+    pub reading: SensorReading,
+    pub health: SensorHealth,
+    /// consecutive failed reads
+    pub failures: u32,
+    /// failed reads since boot
+    pub errors: u32,
+}
+
+impl SensorKind {
+    fn has_temperature(&self) -> bool {
+        matches!(self, SensorKind::Tmp | SensorKind::Pmbus)
+    }
+
+    /// The current is measured across a shunt given in `SensorConfig`.
+    fn has_shunt(&self) -> bool {
+        matches!(self, SensorKind::Ina219 | SensorKind::Ina226)
+    }
+}
+
+pub static SENSORS: Mutex<ThreadModeRawMutex, Vec<Sensor, MAX_SENSORS>> = Mutex::new(Vec::new());
+
+pub type I2cBus = I2c<'static, I2C2, DMA1_CH4, DMA1_CH5>;
+
+/// I2C2 shared between the sensor manager and raw register access over RPC.
+pub static I2C_BUS: Mutex<ThreadModeRawMutex, Option<I2cBus>> = Mutex::new(None);
+
+static BRINGUP_MODE: Mutex<ThreadModeRawMutex, bool> = Mutex::new(false);
+
+pub enum AccessError {
+    Denied,
+    /// the sensor manager is using the bus
+    Busy,
+    Bus(Error),
+}
+
+/// Get the bus for raw access without waiting for a sensor poll to finish.
+fn try_bus() -> Result<MutexGuard<'static, ThreadModeRawMutex, Option<I2cBus>>, AccessError> {
+    I2C_BUS.try_lock().map_err(|_| AccessError::Busy)
+}
+
+/// Get the temperature of the n-th temperature sensor in 1/16°C.
+pub async fn temperature(n: usize) -> i32 {
+    let sensors = SENSORS.lock().await;
+    sensors
+        .iter()
+        .filter(|s| s.config.kind.has_temperature())
+        .nth(n)
+        .map_or(0, |s| s.reading.temp)
+}
+
+/// Get a bitmask of failed sensors, bit n is set if sensor n failed.
+pub async fn failed() -> u32 {
+    let sensors = SENSORS.lock().await;
+    sensors
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.health == SensorHealth::Failed)
+        .fold(0, |mask, (i, _)| mask | (1 << i))
+}
+
+fn temp_fault(n: usize) -> u32 {
+    match n {
+        0 => faults::TEMP1_OVER,
+        1 => faults::TEMP2_OVER,
+        _ => faults::SENSOR_TEMP_OVER,
+    }
+}
+
+/// Scale `value` by 2^`exponent`, saturating at the limits of i32.
+fn scale_pow2(value: i64, exponent: i32) -> i32 {
+    // |value| < 2^27 and exponent in -16..=15 can't overflow an i64
+    let scaled = if exponent >= 0 {
+        value << exponent
+    } else {
+        value >> -exponent
+    };
+    scaled.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// Decode a PMBus LINEAR11 value into milli-units.
+fn linear11_milli(raw: u16) -> i32 {
+    let exponent = (raw as i16 >> 11) as i32;
+    let mantissa = ((raw << 5) as i16 >> 5) as i64;
+    scale_pow2(mantissa * 1000, exponent)
+}
+
+/// Decode a PMBus LINEAR16 output voltage into mV.
+fn linear16_mv(raw: u16, vout_mode: u8) -> i32 {
+    let exponent = ((vout_mode << 3) as i8 >> 3) as i32;
+    scale_pow2(raw as i64 * 1000, exponent)
+}
+
+/// Create the I2C2 driver for the sensor bus.
+pub fn create_bus(peri: I2C2, scl: PB10, sda: PB11, tx_dma: DMA1_CH4, rx_dma: DMA1_CH5) -> I2cBus {
+    let mut config = i2c::Config::default();
+    config.scl_pullup = true;
+    config.sda_pullup = true;
+
+    I2c::new(peri, scl, sda, Irqs, tx_dma, rx_dma, Hertz(100_000), config)
+}
+
+/// Run a transfer with a timeout so a hung DMA transfer or stuck bus can't block forever.
+async fn timed<T>(transfer: impl Future<Output = Result<T, Error>>) -> Result<T, Error> {
+    with_timeout(I2C_TIMEOUT, transfer)
+        .await
+        .unwrap_or(Err(Error::Timeout))
+}
+
+/// Errors that leave the bus or the peripheral in an unknown state.
+fn needs_recovery(e: &Error) -> bool {
+    matches!(
+        e,
+        Error::Timeout | Error::Bus | Error::Arbitration | Error::Overrun
+    )
+}
+
+/// Free a stuck bus and reinitialise the peripheral.
+///
+/// A slave holding SDA low in the middle of a byte is released by clocking out
+/// up to 9 bits on SCL, followed by a STOP condition.
+async fn recover_bus(bus: &mut Option<I2cBus>) {
+    warn!("recovering i2c bus");
+
+    // release the peripheral, DMA channels and pins
+    drop(bus.take());
+
+    {
+        let mut scl = Flex::new(unsafe { PB10::steal() });
+        let mut sda = Flex::new(unsafe { PB11::steal() });
+        scl.set_high();
+        sda.set_high();
+        scl.set_as_input_output(Speed::Low, Pull::Up);
+        sda.set_as_input_output(Speed::Low, Pull::Up);
+
+        for _ in 0..9 {
+            if sda.is_high() {
+                break;
+            }
+            scl.set_low();
+            Timer::after_micros(5).await;
+            scl.set_high();
+            Timer::after_micros(5).await;
+        }
+
+        // STOP condition
+        sda.set_low();
+        Timer::after_micros(5).await;
+        scl.set_high();
+        Timer::after_micros(5).await;
+        sda.set_high();
+        Timer::after_micros(5).await;
+
+        if sda.is_low() {
+            error!("i2c bus still stuck after recovery");
+        }
+    }
+
+    *bus = Some(create_bus(
+        unsafe { I2C2::steal() },
+        unsafe { PB10::steal() },
+        unsafe { PB11::steal() },
+        unsafe { DMA1_CH4::steal() },
+        unsafe { DMA1_CH5::steal() },
+    ));
+}
+
+/// Recover the bus if the result of a transfer asks for it.
+async fn check_bus<T>(bus: &mut Option<I2cBus>, result: &Result<T, Error>) {
+    if let Err(e) = result {
+        if needs_recovery(e) {
+            recover_bus(bus).await;
+        }
+    }
+}
+
+async fn read_be16(i2c: &mut I2cBus, address: u8, reg: u8) -> Result<u16, Error> {
+    let mut data = [0u8; 2];
+    timed(i2c.write_read(address, &[reg], &mut data)).await?;
+    Ok(u16::from_be_bytes(data))
+}
+
+async fn read_le16(i2c: &mut I2cBus, address: u8, reg: u8) -> Result<u16, Error> {
+    let mut data = [0u8; 2];
+    timed(i2c.write_read(address, &[reg], &mut data)).await?;
+    Ok(u16::from_le_bytes(data))
+}
+
+async fn read_u8(i2c: &mut I2cBus, address: u8, reg: u8) -> Result<u8, Error> {
+    let mut data = [0u8; 1];
+    timed(i2c.write_read(address, &[reg], &mut data)).await?;
+    Ok(data[0])
+}
+
+async fn read_sensor(i2c: &mut I2cBus, config: &SensorConfig) -> Result<SensorReading, Error> {
+    let mut reading = SensorReading::default();
+
+    match config.kind {
+        SensorKind::Tmp => {
+            // 12-bit two's complement, 1/16°C per LSB
+            let raw = read_be16(i2c, config.address, 0x00).await?;
+            reading.temp = (raw as i16 >> 4) as i32;
+        }
+        SensorKind::Ina219 => {
+            // 10µV per LSB shunt, 4mV per LSB bus voltage
+            let shunt_uv =
+                read_be16(i2c, config.address, INA_SHUNT_VOLTAGE).await? as i16 as i32 * 10;
+            let bus = read_be16(i2c, config.address, INA_BUS_VOLTAGE).await?;
+            reading.voltage_mv = (bus >> 3) as i32 * 4;
+            reading.current_ma = shunt_uv / config.shunt_mohm;
+        }
+        SensorKind::Ina226 => {
+            // 2.5µV per LSB shunt, 1.25mV per LSB bus voltage
+            let shunt_uv =
+                read_be16(i2c, config.address, INA_SHUNT_VOLTAGE).await? as i16 as i32 * 5 / 2;
+            let bus = read_be16(i2c, config.address, INA_BUS_VOLTAGE).await?;
+            reading.voltage_mv = bus as i32 * 5 / 4;
+            reading.current_ma = shunt_uv / config.shunt_mohm;
+        }
+        SensorKind::Pmbus => {
+            let vout_mode = read_u8(i2c, config.address, PMBUS_VOUT_MODE).await?;
+            let vout = read_le16(i2c, config.address, PMBUS_READ_VOUT).await?;
+            let iout = read_le16(i2c, config.address, PMBUS_READ_IOUT).await?;
+            let pout = read_le16(i2c, config.address, PMBUS_READ_POUT).await?;
+            let temp = read_le16(i2c, config.address, PMBUS_READ_TEMPERATURE_1).await?;
+            reading.voltage_mv = linear16_mv(vout, vout_mode);
+            reading.current_ma = linear11_milli(iout);
+            reading.power_mw = linear11_milli(pout);
+            reading.temp = (linear11_milli(temp) as i64 * 16 / 1000) as i32;
+        }
+    }
+
+    if config.kind != SensorKind::Pmbus {
+        reading.power_mw = (reading.voltage_mv as i64 * reading.current_ma as i64 / 1000) as i32;
+    }
+
+    Ok(reading)
+}
+
+/// Find out what kind of sensor answers on the given address.
+async fn identify(i2c: &mut I2cBus, address: u8) -> Option<SensorKind> {
+    if (0x40..=0x4f).contains(&address) {
+        if let Ok(INA226_TI_ID) = read_be16(i2c, address, INA226_MANUFACTURER_ID).await {
+            return Some(SensorKind::Ina226);
+        }
+    }
+
+    if (0x48..=0x4b).contains(&address) {
+        return Some(SensorKind::Tmp);
+    }
+
+    // PMBus revision is 0x11, 0x22 or 0x33 for part I and II revisions 1.1 to 1.3
+    match read_u8(i2c, address, PMBUS_REVISION).await {
+        Ok(0x11) | Ok(0x22) | Ok(0x33) => Some(SensorKind::Pmbus),
+        _ => None,
+    }
+}
+
+/// Get the addresses of all devices acknowledging a read. `task` checks in
+/// for every address, a bus that stays stuck after recovery ends the scan.
+async fn scan_bus(bus: &mut Option<I2cBus>, task: Task) -> Result<Vec<u8, 112>, Error> {
+    let mut found = Vec::new();
+    let mut failures = 0;
+
+    for address in 0x08..0x78 {
+        watchdog::check_in(task);
+        let mut data = [0u8; 1];
+        let result = timed(unwrap!(bus.as_mut()).read(address, &mut data)).await;
+        check_bus(bus, &result).await;
+        match result {
+            Ok(_) => {
+                let _ = found.push(address);
+                failures = 0;
+            }
+            Err(e) if needs_recovery(&e) => {
+                failures += 1;
+                if failures >= MAX_FAILURES {
+                    error!("i2c bus stuck, scan aborted at {:x}", address);
+                    return Err(e);
+                }
+            }
+            // no device acknowledging
+            Err(_) => failures = 0,
+        }
+    }
+
+    Ok(found)
+}
+
+/// Add the sensors found on the bus to the configured ones.
+async fn discover(bus: &mut Option<I2cBus>, found: &mut Vec<SensorConfig, MAX_SENSORS>) {
+    let Ok(addresses) = scan_bus(bus, Task::Sensors).await else {
+        return;
+    };
+
+    for address in addresses {
+        watchdog::check_in(Task::Sensors);
+        if found.iter().any(|config| config.address == address) {
+            continue;
+        }
+        let Some(kind) = identify(unwrap!(bus.as_mut()), address).await else {
+            debug!("unknown device at {:x}", address);
+            continue;
+        };
+
+        info!("found {} at {:x}", kind, address);
+
+        let config = SensorConfig {
+            kind,
+            address,
+            shunt_mohm: DEFAULT_SHUNT_MOHM,
+        };
+
+        if found.push(config).is_err() {
+            warn!("too many sensors, ignoring {:x}", address);
+        }
+    }
+}
+
+/// Scan I2C2 for responding addresses.
+pub async fn scan() -> Result<Vec<u8, 112>, AccessError> {
+    let mut bus = try_bus()?;
+    scan_bus(&mut bus, Task::Rpc)
+        .await
+        .map_err(AccessError::Bus)
+}
+
+/// Read `data.len()` bytes starting at register `reg`.
+pub async fn read_register(address: u8, reg: u8, data: &mut [u8]) -> Result<(), AccessError> {
+    let mut bus = try_bus()?;
+    let result = timed(unwrap!(bus.as_mut()).write_read(address, &[reg], data)).await;
+    check_bus(&mut bus, &result).await;
+    result.map_err(AccessError::Bus)
+}
+
+/// Write `data` starting at register `reg`.
+///
+/// Writes to the addresses of the sensors in use are only allowed in bring-up
+/// mode so the sensor configuration can't be changed by accident.
+pub async fn write_register(address: u8, reg: u8, data: &[u8]) -> Result<(), AccessError> {
+    let is_sensor = SENSORS
+        .lock()
+        .await
+        .iter()
+        .any(|s| s.config.address == address);
+    if is_sensor && !*BRINGUP_MODE.lock().await {
+        warn!("write to sensor at {:x} denied", address);
+        return Err(AccessError::Denied);
+    }
+
+    let mut buf = [0u8; MAX_TRANSFER + 1];
+    buf[0] = reg;
+    buf[1..=data.len()].copy_from_slice(data);
+
+    let mut bus = try_bus()?;
+    let result = timed(unwrap!(bus.as_mut()).write(address, &buf[..=data.len()])).await;
+    check_bus(&mut bus, &result).await;
+    result.map_err(AccessError::Bus)
+}
+
+/// Enter bring-up mode with the right key, leave it with any other.
+pub async fn set_bringup_mode(key: u32) -> bool {
+    let enabled = key == BRINGUP_KEY;
+    info!("bring-up mode: {}", enabled);
+    *BRINGUP_MODE.lock().await = enabled;
+    enabled
+}
+
+/// Count a failed read and update the sensor's health.
+async fn sensor_failed(i: usize, config: &SensorConfig) {
+    {
+        let mut sensors = SENSORS.lock().await;
+        let sensor = &mut sensors[i];
+        sensor.failures += 1;
+        sensor.errors += 1;
+
+        let health = if sensor.failures >= MAX_FAILURES {
+            SensorHealth::Failed
+        } else {
+            SensorHealth::Degraded
+        };
+        if health != sensor.health {
+            warn!("{} at {:x}: {}", config.kind, config.address, health);
+        }
+        sensor.health = health;
+    }
+    update_failed_fault().await;
+}
+
+/// A failed temperature sensor means the board runs unmonitored. It's reported
+/// as a fault next to the sensor's health and `QState.sensors_failed`, the
+/// ASICs keep running unless the fault policy says otherwise.
+async fn update_failed_fault() {
+    let any_failed = SENSORS
+        .lock()
+        .await
+        .iter()
+        .any(|s| s.config.kind.has_temperature() && s.health == SensorHealth::Failed);
+    faults::update(faults::SENSOR_FAILED, any_failed).await;
+}
+
+#[embassy_executor::task]
+pub async fn sensor_manager() {
+    let mut configs: Vec<SensorConfig, MAX_SENSORS> = unwrap!(Vec::from_slice(SENSOR_CONFIG));
+    discover(&mut *I2C_BUS.lock().await, &mut configs).await;
+    // the current would be divided by the shunt
+    configs.retain(|config| {
+        let valid = !config.kind.has_shunt() || config.shunt_mohm > 0;
+        if !valid {
+            error!(
+                "no shunt for {} at {:x}, not polled",
+                config.kind, config.address
+            );
+        }
+        valid
+    });
+
+    {
+        let mut sensors = SENSORS.lock().await;
+        for config in configs.iter() {
+            let _ = sensors.push(Sensor {
+                config: *config,
+                reading: SensorReading::default(),
+                health: SensorHealth::Unknown,
+                failures: 0,
+                errors: 0,
+            });
+        }
+    }
+
+    loop {
+        watchdog::check_in(Task::Sensors);
+        Timer::after_millis(5000).await;
+
+        let mut temp_index = 0;
+
+        for (i, config) in configs.iter().enumerate() {
+            let temp_n = temp_index;
+            if config.kind.has_temperature() {
+                temp_index += 1;
+            }
+
+            let result = {
+                let mut bus = I2C_BUS.lock().await;
+                let result = read_sensor(unwrap!(bus.as_mut()), config).await;
+                check_bus(&mut bus, &result).await;
+                result
+            };
+
+            let reading = match result {
+                Ok(reading) => reading,
+                Err(e) => {
+                    error!("i2c error: {:?}", e);
+                    sensor_failed(i, config).await;
+                    continue;
+                }
+            };
+
+            info!(
+                "read {} at {:x}: temp: {}, voltage: {}mV, current: {}mA, power: {}mW",
+                config.kind,
+                config.address,
+                reading.temp,
+                reading.voltage_mv,
+                reading.current_ma,
+                reading.power_mw
+            );
+
+            {
+                let mut sensors = SENSORS.lock().await;
+                let sensor = &mut sensors[i];
+                sensor.reading = reading;
+                sensor.health = SensorHealth::Ok;
+                sensor.failures = 0;
+            }
+            update_failed_fault().await;
+
+            if config.kind.has_temperature() {
+                faults::check_limits(
+                    temp_fault(temp_n),
+                    reading.temp,
+                    TEMP_MIN,
+                    TEMP_MAX,
+                    TEMP_HYST,
+                )
+                .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use defmt::*;
+use embassy_stm32::peripherals::IWDG;
+use embassy_stm32::wdg::IndependentWatchdog;
+use embassy_time::{Instant, Timer};
+
+// the supervisor feeds every 500ms, the watchdog bites after 2s without
+const WATCHDOG_TIMEOUT_US: u32 = 2_000_000;
+const FEED_INTERVAL_MS: u64 = 500;
+
+// marks a task waiting for the host, it can't be stuck there
+const IDLE: u32 = u32::MAX;
+
+/// Tasks that have to check in for the watchdog to be fed.
+#[derive(Clone, Copy, Format)]
+pub enum Task {
+    Sensors = 0,
+    Adc = 1,
+    Pwm = 2,
+    PowerGood = 3,
+    Rpc = 4,
+}
+
+const TASKS: [Task; 5] = [
+    Task::Sensors,
+    Task::Adc,
+    Task::Pwm,
+    Task::PowerGood,
+    Task::Rpc,
+];
+
+impl Task {
+    /// Longest time between two check-ins.
+    fn timeout_ms(self) -> u32 {
+        match self {
+            // a poll of all sensors including timeouts and bus recovery
+            Task::Sensors => 15_000,
+            // a kick-start of up to 5s
+            Task::Pwm => 10_000,
+            Task::Adc | Task::PowerGood | Task::Rpc => 5_000,
+        }
+    }
+}
+
+// time of the last check-in per task in ms since boot
+static CHECK_INS: [AtomicU32; TASKS.len()] = [
+    AtomicU32::new(0),
+    AtomicU32::new(0),
+    AtomicU32::new(0),
+    AtomicU32::new(0),
+    AtomicU32::new(0),
+];
+
+fn now_ms() -> u32 {
+    Instant::now().as_millis() as u32
+}
+
+/// Report the task as alive.
+pub fn check_in(task: Task) {
+    // IDLE is reserved, 49 days of uptime are off by a ms at most
+    let now = now_ms().min(IDLE - 1);
+    CHECK_INS[task as usize].store(now, Ordering::Relaxed);
+}
+
+/// Report the task as waiting for an external event for an unknown time.
+pub fn idle(task: Task) {
+    CHECK_INS[task as usize].store(IDLE, Ordering::Relaxed);
+}
+
+/// Get the first task that didn't check in in time.
+fn stuck_task() -> Option<Task> {
+    let now = now_ms();
+    TASKS.iter().copied().find(|task| {
+        let check_in = CHECK_INS[*task as usize].load(Ordering::Relaxed);
+        check_in != IDLE && now.wrapping_sub(check_in) > task.timeout_ms()
+    })
+}
+
+/// Feed the independent watchdog as long as all critical tasks are alive.
+///
+/// When a task hangs the MCU is reset by the watchdog and starts with the
+/// ASIC rail off and the fans at full speed.
+#[embassy_executor::task]
+pub async fn supervisor(iwdg: IWDG) {
+    let mut wdg = IndependentWatchdog::new(iwdg, WATCHDOG_TIMEOUT_US);
+    wdg.unleash();
+
+    loop {
+        match stuck_task() {
+            None => wdg.pet(),
+            Some(task) => error!("task {} stuck, waiting for watchdog reset", task),
+        }
+        Timer::after_millis(FEED_INTERVAL_MS).await;
+    }
+}
@@ -0,0 +1,36 @@
+//! Generates the Rust bindings of `coms.proto` and checks that the python
+//! bindings were generated from the same file, so the two can't drift apart.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+
+use qaxe_protogen::{check_python, generate, Options, Proto};
+
+const PROTO: &str = "src/protobuf/coms.proto";
+const OPTIONS: &str = "src/protobuf/coms.options";
+const PYTHON: &str = "src/protobuf/coms_pb2.py";
+
+fn read(path: &str) -> Result<String, String> {
+    println!("cargo:rerun-if-changed={}", path);
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn run() -> Result<(), String> {
+    let proto = Proto::parse(&read(PROTO)?).map_err(|e| format!("{}: {}", PROTO, e))?;
+    let options = Options::parse(&read(OPTIONS)?).map_err(|e| format!("{}: {}", OPTIONS, e))?;
+
+    check_python("coms.proto", &proto, &read(PYTHON)?).map_err(|e| format!("{}: {}", PYTHON, e))?;
+
+    let code = generate("coms.proto", &proto, &options)?;
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("coms.rs");
+    fs::write(&out, code).map_err(|e| format!("{}: {}", out.display(), e))
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+//! Any `QControl` payload in an otherwise valid request.
+
+use libfuzzer_sys::fuzz_target;
+use qaxe_core::framing::MAX_FRAME;
+use qaxe_core::protobuf::coms::QRequest;
+use qaxe_core::protobuf::serialize_into_slice;
+use qaxe_core::rpc::Commands;
+use qaxe_core_fuzz::harness::{check_frame, FullDevice};
+
+fuzz_target!(|data: &[u8]| {
+    let request = QRequest {
+        id: 1,
+        op: Commands::Control as i32,
+        data,
+        ..Default::default()
+    };
+    let mut frame = [0u8; MAX_FRAME];
+    if let Ok(len) = serialize_into_slice(&request, &mut frame) {
+        check_frame(&mut FullDevice::default(), &frame[..len]);
+    }
+});
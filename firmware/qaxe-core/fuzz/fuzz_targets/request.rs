@@ -0,0 +1,13 @@
+#![no_main]
+
+//! Any bytes from the host, as handed over by the deframer.
+
+use libfuzzer_sys::fuzz_target;
+use qaxe_core::framing::MAX_FRAME;
+use qaxe_core_fuzz::harness::{check_frame, FullDevice};
+
+fuzz_target!(|frame: &[u8]| {
+    if frame.len() <= MAX_FRAME {
+        check_frame(&mut FullDevice::default(), frame);
+    }
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+//! Responses to any request decode to what was sent and encode to the same
+//! bytes again.
+
+use libfuzzer_sys::fuzz_target;
+use qaxe_core::framing::MAX_FRAME;
+use qaxe_core::protobuf::coms::QResponse;
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core_fuzz::harness::{check_frame, FullDevice};
+
+fuzz_target!(|frame: &[u8]| {
+    if frame.len() > MAX_FRAME {
+        return;
+    }
+    let bytes = check_frame(&mut FullDevice::default(), frame);
+
+    let response: QResponse = deserialize_from_slice(&bytes).unwrap();
+    let mut again = [0u8; MAX_FRAME];
+    let len = serialize_into_slice(&response, &mut again).unwrap();
+    assert_eq!(&again[..len], &bytes[..]);
+});
@@ -0,0 +1,4 @@
+//! Harness of the fuzz targets, the same the property tests use.
+
+#[path = "../../tests/common/harness.rs"]
+pub mod harness;
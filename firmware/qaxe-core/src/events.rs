@@ -0,0 +1,114 @@
+use heapless::Deque;
+
+use crate::protobuf::coms::{QEvent, QResponse};
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventKind {
+    /// value: active fault bits
+    Fault = 1,
+    /// value: 1 with PGOOD, 0 without
+    Power = 2,
+    /// value: bit n set for each stalled fan
+    FanStall = 3,
+    /// value: ms since the last request
+    HostTimeout = 4,
+}
+
+impl EventKind {
+    /// Bit of the kind in a subscription mask.
+    pub const fn mask(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Event {
+    pub seq: u32,
+    pub timestamp_ms: u32,
+    pub kind: EventKind,
+    pub value: i32,
+}
+
+impl Event {
+    pub fn to_message(&self) -> QEvent {
+        QEvent {
+            seq: self.seq as i32,
+            timestamp: self.timestamp_ms as i32,
+            kind: self.kind as i32,
+            value: self.value,
+        }
+    }
+
+    /// Wrap the event for sending it unsolicited on the control channel.
+    pub fn to_response(&self) -> QResponse<'static> {
+        QResponse {
+            event: Some(self.to_message()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Backlog of the latest `N` events and how far they were sent to the host.
+///
+/// Events are kept while no host listens, when the backlog is full the oldest
+/// event is dropped. The host sees the gap in the sequence numbers.
+pub struct EventLog<const N: usize> {
+    events: Deque<Event, N>,
+    next_seq: u32,
+    // first event not sent to the host yet
+    unsent: u32,
+}
+
+impl<const N: usize> Default for EventLog<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventLog<N> {
+    pub const fn new() -> Self {
+        EventLog {
+            events: Deque::new(),
+            next_seq: 0,
+            unsent: 0,
+        }
+    }
+
+    pub fn push(&mut self, timestamp_ms: u32, kind: EventKind, value: i32) -> Event {
+        let event = Event {
+            seq: self.next_seq,
+            timestamp_ms,
+            kind,
+            value,
+        };
+        self.next_seq = self.next_seq.wrapping_add(1);
+
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        // can't fail, there is room after dropping the oldest one
+        let _ = self.events.push_back(event);
+        event
+    }
+
+    /// Get the oldest event not sent yet of the kinds in `mask`.
+    ///
+    /// Events of other kinds are skipped for good.
+    pub fn next_unsent(&mut self, mask: u32) -> Option<Event> {
+        for event in self.events.iter() {
+            if event.seq.wrapping_sub(self.unsent) as i32 >= 0 {
+                if event.kind.mask() & mask != 0 {
+                    return Some(*event);
+                }
+                self.unsent = event.seq.wrapping_add(1);
+            }
+        }
+        None
+    }
+
+    /// Mark the event as delivered.
+    pub fn sent(&mut self, event: &Event) {
+        self.unsent = event.seq.wrapping_add(1);
+    }
+}
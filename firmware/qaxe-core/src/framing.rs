@@ -0,0 +1,188 @@
+use heapless::Vec;
+
+/// Largest frame on the control channel, including the length prefix.
+pub const MAX_FRAME: usize = 256;
+
+// a u32 length takes at most 5 varint bytes
+const MAX_PREFIX: usize = 5;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameError {
+    /// The frame doesn't fit the buffer, its bytes are dropped.
+    Oversized,
+    /// The length prefix isn't a valid varint, buffered data is dropped.
+    InvalidLength,
+}
+
+/// Decode a varint length prefix, `None` if more bytes are needed.
+fn decode_prefix(data: &[u8]) -> Result<Option<(usize, usize)>, FrameError> {
+    let mut value = 0usize;
+    for (i, byte) in data.iter().take(MAX_PREFIX).enumerate() {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(Some((value, i + 1)));
+        }
+    }
+
+    if data.len() >= MAX_PREFIX {
+        Err(FrameError::InvalidLength)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Reassembles varint length-delimited frames from a byte stream.
+///
+/// Frames may be split over several packets or share a packet with other
+/// frames. The returned frames include the length prefix, as expected by
+/// `protobuf::deserialize_from_slice`.
+pub struct Deframer<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    // length of the frame handed out last, dropped on the next call
+    consumed: usize,
+    // remaining bytes of an oversized frame
+    skip: usize,
+}
+
+impl<const N: usize> Default for Deframer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deframer<N> {
+    pub const fn new() -> Self {
+        Deframer {
+            buf: [0u8; N],
+            len: 0,
+            consumed: 0,
+            skip: 0,
+        }
+    }
+
+    /// Drop all buffered data, e.g. after a reconnect.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.consumed = 0;
+        self.skip = 0;
+    }
+
+    fn drop_consumed(&mut self) {
+        if self.consumed > 0 {
+            self.buf.copy_within(self.consumed..self.len, 0);
+            self.len -= self.consumed;
+            self.consumed = 0;
+        }
+    }
+
+    /// Append received bytes, returns how many were taken.
+    ///
+    /// Fewer bytes than given are only taken when the buffer is full, the rest
+    /// has to be pushed again after the pending frames were taken out with
+    /// [`Deframer::next_frame`].
+    pub fn push(&mut self, data: &[u8]) -> usize {
+        self.drop_consumed();
+
+        let skipped = self.skip.min(data.len());
+        self.skip -= skipped;
+
+        let n = (N - self.len).min(data.len() - skipped);
+        self.buf[self.len..self.len + n].copy_from_slice(&data[skipped..skipped + n]);
+        self.len += n;
+
+        skipped + n
+    }
+
+    /// Take out the next complete frame, `None` if more data is needed.
+    pub fn next_frame(&mut self) -> Option<Result<&[u8], FrameError>> {
+        self.drop_consumed();
+
+        let (length, prefix) = match decode_prefix(&self.buf[..self.len]) {
+            Ok(Some(prefix)) => prefix,
+            Ok(None) => return None,
+            Err(e) => {
+                self.len = 0;
+                return Some(Err(e));
+            }
+        };
+
+        let total = prefix.saturating_add(length);
+        if total > N {
+            // there is no way to resync inside the frame, skip over it
+            self.skip = total - self.len;
+            self.len = 0;
+            return Some(Err(FrameError::Oversized));
+        }
+
+        if self.len < total {
+            return None;
+        }
+
+        self.consumed = total;
+        Some(Ok(&self.buf[..total]))
+    }
+}
+
+/// What a host speaks on the control channel.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Protocol {
+    /// Length-delimited `QRequest`s.
+    Protobuf,
+    /// Text commands typed into a serial terminal, see `shell`.
+    Shell,
+    /// JSON-RPC requests, one per line, see `jsonrpc`.
+    JsonRpc,
+}
+
+// typed characters and line ends, never the field tag of a `QRequest`, the
+// tag of `id` is a backspace
+fn is_text(byte: u8) -> bool {
+    byte.is_ascii_graphic() || matches!(byte, b' ' | b'\r' | b'\n')
+}
+
+/// Tells the protocol of a connection from the first bytes the host sends.
+///
+/// A frame starts with its length and the tag of the first field of the
+/// `QRequest`, which is never a printable character since the request has no
+/// fields 4 to 15. Text starts with two characters, or a line end, and is
+/// JSON-RPC when the first is the `{` of a request object or the `[` of a
+/// batch.
+#[derive(Default)]
+pub struct Detector {
+    first: Option<u8>,
+}
+
+impl Detector {
+    pub const fn new() -> Self {
+        Detector { first: None }
+    }
+
+    /// Start over, e.g. after a reconnect.
+    pub fn clear(&mut self) {
+        self.first = None;
+    }
+
+    /// Look at the next received byte. Once the protocol is known it's
+    /// returned with the bytes it was told from, they are the start of the
+    /// first frame or line.
+    pub fn push(&mut self, byte: u8) -> Option<(Protocol, Vec<u8, 2>)> {
+        let Some(first) = self.first.take() else {
+            if !is_text(byte) {
+                // e.g. the empty frame of a `Nop` without id
+                return Some((Protocol::Protobuf, Vec::from_slice(&[byte]).unwrap()));
+            }
+            self.first = Some(byte);
+            return None;
+        };
+
+        let protocol = match (first, is_text(byte)) {
+            (_, false) => Protocol::Protobuf,
+            (b'{' | b'[', true) => Protocol::JsonRpc,
+            (_, true) => Protocol::Shell,
+        };
+        Some((protocol, Vec::from_slice(&[first, byte]).unwrap()))
+    }
+}
@@ -0,0 +1,182 @@
+use heapless::Deque;
+
+use crate::protobuf::coms::{QHistory, QHistoryInterval};
+use crate::rpc::framed_size;
+
+/// One telemetry sample, taken periodically.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    /// 1/16°C
+    pub temps: [i32; 2],
+    /// per-mille
+    pub duties: [u16; 2],
+}
+
+/// Summary of the samples and events of one interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub seq: u32,
+    /// ms since boot when the interval was closed
+    pub end_ms: u32,
+    pub samples: u32,
+    pub temp_min: [i32; 2],
+    pub temp_max: [i32; 2],
+    pub temp_avg: [i32; 2],
+    pub duty_avg: [u16; 2],
+    pub pgood_losses: u32,
+    /// fault bits that were active at some time in the interval
+    pub faults: u32,
+}
+
+impl Interval {
+    pub fn to_message(&self) -> QHistoryInterval {
+        QHistoryInterval {
+            seq: self.seq as i32,
+            end: self.end_ms as i32,
+            samples: self.samples as i32,
+            temp1_min: self.temp_min[0],
+            temp2_min: self.temp_min[1],
+            temp1_max: self.temp_max[0],
+            temp2_max: self.temp_max[1],
+            temp1_avg: self.temp_avg[0],
+            temp2_avg: self.temp_avg[1],
+            pwm1_avg: self.duty_avg[0] as i32,
+            pwm2_avg: self.duty_avg[1] as i32,
+            pgood_losses: self.pgood_losses as i32,
+            faults: self.faults as i32,
+        }
+    }
+}
+
+/// The interval being collected.
+#[derive(Default)]
+struct Accumulator {
+    samples: u32,
+    temp_min: [i32; 2],
+    temp_max: [i32; 2],
+    temp_sum: [i64; 2],
+    duty_sum: [u32; 2],
+    pgood_losses: u32,
+    faults: u32,
+}
+
+/// Summaries of the latest `N` intervals.
+///
+/// The oldest interval is dropped when the buffer is full, the host sees it
+/// in the sequence numbers like with the events.
+pub struct History<const N: usize> {
+    intervals: Deque<Interval, N>,
+    current: Accumulator,
+    next_seq: u32,
+}
+
+impl<const N: usize> Default for History<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> History<N> {
+    pub const fn new() -> Self {
+        History {
+            intervals: Deque::new(),
+            current: Accumulator {
+                samples: 0,
+                temp_min: [0; 2],
+                temp_max: [0; 2],
+                temp_sum: [0; 2],
+                duty_sum: [0; 2],
+                pgood_losses: 0,
+                faults: 0,
+            },
+            next_seq: 0,
+        }
+    }
+
+    pub fn add_sample(&mut self, sample: Sample) {
+        let acc = &mut self.current;
+        for i in 0..2 {
+            let temp = sample.temps[i];
+            if acc.samples == 0 {
+                acc.temp_min[i] = temp;
+                acc.temp_max[i] = temp;
+            } else {
+                acc.temp_min[i] = acc.temp_min[i].min(temp);
+                acc.temp_max[i] = acc.temp_max[i].max(temp);
+            }
+            acc.temp_sum[i] += temp as i64;
+            acc.duty_sum[i] += sample.duties[i] as u32;
+        }
+        acc.samples += 1;
+    }
+
+    /// Count a loss of PGOOD.
+    pub fn add_pgood_loss(&mut self) {
+        self.current.pgood_losses += 1;
+    }
+
+    /// Note the fault bits active now.
+    pub fn add_faults(&mut self, faults: u32) {
+        self.current.faults |= faults;
+    }
+
+    /// Close the current interval and start the next one.
+    pub fn close(&mut self, end_ms: u32) -> Interval {
+        let acc = core::mem::take(&mut self.current);
+        let avg = |sum: i64| {
+            if acc.samples == 0 {
+                0
+            } else {
+                sum / acc.samples as i64
+            }
+        };
+
+        let interval = Interval {
+            seq: self.next_seq,
+            end_ms,
+            samples: acc.samples,
+            temp_min: acc.temp_min,
+            temp_max: acc.temp_max,
+            temp_avg: acc.temp_sum.map(|sum| avg(sum) as i32),
+            duty_avg: acc.duty_sum.map(|sum| avg(sum as i64) as u16),
+            pgood_losses: acc.pgood_losses,
+            faults: acc.faults,
+        };
+        self.next_seq = self.next_seq.wrapping_add(1);
+
+        if self.intervals.is_full() {
+            self.intervals.pop_front();
+        }
+        // can't fail, there is room after dropping the oldest one
+        let _ = self.intervals.push_back(interval);
+        interval
+    }
+
+    /// Get the intervals from `start` on, as many as fit into `max_size`
+    /// bytes and the page, and at most `count` of them, all that fit with 0.
+    pub fn page(&self, start: u32, count: usize, max_size: usize) -> QHistory {
+        let mut page = QHistory {
+            next: self.next_seq as i32,
+            ..Default::default()
+        };
+
+        let intervals = self
+            .intervals
+            .iter()
+            .filter(|interval| interval.seq.wrapping_sub(start) as i32 >= 0);
+        for interval in intervals {
+            if (count != 0 && page.intervals.len() >= count) || page.intervals.is_full() {
+                page.next = interval.seq as i32;
+                break;
+            }
+            // can't fail, checked above
+            let _ = page.intervals.push(interval.to_message());
+            if framed_size(&page) > max_size {
+                page.intervals.pop();
+                page.next = interval.seq as i32;
+                break;
+            }
+        }
+        page
+    }
+}
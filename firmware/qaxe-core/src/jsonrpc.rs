@@ -0,0 +1,469 @@
+//! JSON-RPC 2.0 on the control channel, one request or response per line,
+//! for scripts and web tools without protobuf bindings. The methods are the
+//! commands of `QRequest.body`, run by `rpc::process_request`.
+
+use heapless::Vec;
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::framing::MAX_FRAME;
+use crate::protobuf::coms::{
+    QBringUp, QControl, QEmpty, QHistory, QHistoryInterval, QHistoryRequest, QI2cScan,
+    QI2cTransfer, QPwmConfig, QResetInfo, QSensor, QSensors, QState,
+};
+use crate::rpc::{
+    run_command, Body, Device, Error, Errors, Reply, MAX_RESPONSE_DATA, MAX_TRANSFER,
+};
+
+/// Longest request line, longer requests are rejected.
+pub const MAX_LINE: usize = MAX_FRAME;
+/// Largest response line.
+pub const MAX_OUTPUT: usize = 1024;
+/// Most history intervals in a response, each takes about 250 bytes of JSON.
+pub const HISTORY_PAGE: i32 = 3;
+
+pub type Output = Vec<u8, MAX_OUTPUT>;
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+/// Serialize messages as JSON objects with the field names of `coms.proto`.
+macro_rules! serialize_fields {
+    ($($message:ty { $($field:ident),* $(,)? })*) => {$(
+        impl Serialize for $message {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let len = [$(stringify!($field)),*].len();
+                let mut object = serializer.serialize_struct(stringify!($message), len)?;
+                $(object.serialize_field(stringify!($field), &self.$field)?;)*
+                object.end()
+            }
+        }
+    )*};
+}
+
+serialize_fields! {
+    QState {
+        pgood_1v2, temp1, temp2, vin_mv, v1v2_mv, vdda_mv, mcu_temp, faults, sensors_failed,
+        pwm1_applied, pwm2_applied, pwm1_target, pwm2_target, fan1_rpm, fan2_rpm, timestamp_us,
+    }
+    QSensor { address, kind, temp, voltage_mv, current_ma, power_mw, health, errors }
+    QSensors { sensors }
+    QI2cScan<'_> { addresses }
+    QI2cTransfer<'_> { address, reg, length, data }
+    QPwmConfig {
+        frequency, invert1, invert2, min_duty1, min_duty2, kick_duty, kick_ms, ramp_rate,
+        shutdown_on, full_fans_on,
+    }
+    QResetInfo<'_> { cause, boots, panic }
+    QHistoryInterval {
+        seq, end, samples, temp1_min, temp2_min, temp1_max, temp2_max, temp1_avg, temp2_avg,
+        pwm1_avg, pwm2_avg, pgood_losses, faults,
+    }
+    QHistory { intervals, next }
+}
+
+// Parameters of the methods, fields left out are 0 like in protobuf. The
+// messages are mirrored since serde-json-core can't borrow `bytes`.
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Control {
+    state_1v2: i32,
+    pwm1: i32,
+    pwm2: i32,
+    permille: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Transfer {
+    address: i32,
+    reg: i32,
+    length: i32,
+    data: Vec<u8, MAX_TRANSFER>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct BringUp {
+    key: i32,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct PwmConfig {
+    frequency: i32,
+    invert1: bool,
+    invert2: bool,
+    min_duty1: i32,
+    min_duty2: i32,
+    kick_duty: i32,
+    kick_ms: i32,
+    ramp_rate: i32,
+    shutdown_on: i32,
+    full_fans_on: i32,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct History {
+    start: i32,
+    count: i32,
+}
+
+#[derive(Deserialize)]
+struct Params<P: Default> {
+    #[serde(default)]
+    params: P,
+}
+
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    #[serde(default)]
+    jsonrpc: &'a str,
+    #[serde(default)]
+    method: &'a str,
+}
+
+// `id` is `None` when left out and `Some(None)` when null, a request with a
+// null id is answered, one without is a notification
+
+#[derive(Deserialize)]
+struct NumberId {
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Option<i64>>,
+}
+
+#[derive(Deserialize)]
+struct StringId<'a> {
+    #[serde(default, deserialize_with = "present", borrow)]
+    id: Option<Option<&'a str>>,
+}
+
+fn present<'de, T: Deserialize<'de>, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Clone, Copy)]
+enum Id<'a> {
+    Number(i64),
+    String(&'a str),
+    Null,
+}
+
+impl Serialize for Id<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Id::Number(id) => serializer.serialize_i64(*id),
+            Id::String(id) => serializer.serialize_str(id),
+            Id::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Field {
+    field: &'static str,
+}
+
+/// The error object of a response, device errors keep their code of
+/// `QResponse.error`.
+#[derive(Serialize)]
+struct Failure {
+    code: i32,
+    message: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Field>,
+}
+
+impl Failure {
+    const fn new(code: i32, message: &'static str) -> Self {
+        Failure {
+            code,
+            message,
+            data: None,
+        }
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Failure {
+            code: error.code as i32,
+            message: Errors::to_string(&error.code),
+            data: error.field.map(|field| Field { field }),
+        }
+    }
+}
+
+impl From<Errors> for Failure {
+    fn from(code: Errors) -> Self {
+        Error::from(code).into()
+    }
+}
+
+#[derive(Serialize)]
+struct Success<'a, R> {
+    jsonrpc: &'static str,
+    result: R,
+    id: Id<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse<'a> {
+    jsonrpc: &'static str,
+    error: Failure,
+    id: Option<Id<'a>>,
+}
+
+/// Collects request lines and answers them.
+#[derive(Default)]
+pub struct JsonRpc {
+    line: Vec<u8, MAX_LINE>,
+    // the line got too long and is rejected at its end
+    overflow: bool,
+}
+
+impl JsonRpc {
+    pub const fn new() -> Self {
+        JsonRpc {
+            line: Vec::new(),
+            overflow: false,
+        }
+    }
+
+    /// Drop the line received so far, e.g. after the host went away.
+    pub fn clear(&mut self) {
+        self.line.clear();
+        self.overflow = false;
+    }
+
+    /// Whether a line was started and not ended yet.
+    pub fn pending(&self) -> bool {
+        !self.line.is_empty() || self.overflow
+    }
+
+    /// Take a received byte. Returns true at the end of a line, answer it
+    /// with [`JsonRpc::run`].
+    pub fn push(&mut self, byte: u8) -> bool {
+        match byte {
+            b'\n' => true,
+            // CRLF from terminals and Windows tools
+            b'\r' => false,
+            _ => {
+                if self.overflow || self.line.push(byte).is_err() {
+                    self.overflow = true;
+                }
+                false
+            }
+        }
+    }
+
+    /// Run the request ended last and write its response line into `out`.
+    /// Notifications, i.e. requests without id, and empty lines get none,
+    /// `out` is left empty then.
+    pub async fn run<D: Device>(&mut self, device: &mut D, out: &mut Output) {
+        out.clear();
+        let line = core::mem::take(&mut self.line);
+        if core::mem::take(&mut self.overflow) {
+            let failure = Failure::new(INVALID_REQUEST, "request too long");
+            write_error(out, None, failure);
+            return;
+        }
+        let Some(&start) = line.iter().find(|byte| !byte.is_ascii_whitespace()) else {
+            return;
+        };
+        if start == b'[' {
+            let failure = Failure::new(INVALID_REQUEST, "batches need protobuf");
+            write_error(out, None, failure);
+            return;
+        }
+
+        let (method, id) = match parse(&line) {
+            Ok(request) => request,
+            Err((id, failure)) => {
+                write_error(out, id, failure);
+                return;
+            }
+        };
+        let mut data = [0u8; MAX_RESPONSE_DATA];
+        let result = call(device, method, &line, &mut data).await;
+        let Some(id) = id else {
+            return;
+        };
+        let written = match result {
+            Ok(reply) => write_reply(out, &reply, id),
+            Err(failure) => write(out, &error_response(Some(id), failure)),
+        };
+        if written.is_err() {
+            write_error(out, Some(id), Errors::ErrorSerializingResponseData.into());
+        }
+    }
+}
+
+/// The method and id of a request. The errors carry the id if it was read.
+#[allow(clippy::type_complexity)]
+fn parse(line: &[u8]) -> Result<(&str, Option<Id<'_>>), (Option<Id<'_>>, Failure)> {
+    let id = match serde_json_core::from_slice::<NumberId>(line) {
+        Ok((NumberId { id }, _)) => id.map(|id| id.map_or(Id::Null, Id::Number)),
+        // string ids are echoed as read, so they can't have escapes
+        Err(_) => match serde_json_core::from_slice::<StringId>(line) {
+            Ok((StringId { id }, _)) if !id.flatten().unwrap_or_default().contains('\\') => {
+                id.map(|id| id.map_or(Id::Null, Id::String))
+            }
+            Ok(_) => return Err((None, Failure::new(INVALID_REQUEST, "invalid id"))),
+            Err(_) => return Err((None, Failure::new(PARSE_ERROR, "parse error"))),
+        },
+    };
+    let envelope = match serde_json_core::from_slice::<Envelope>(line) {
+        Ok((envelope, _)) => envelope,
+        Err(_) => return Err((id, Failure::new(INVALID_REQUEST, "invalid request"))),
+    };
+    if envelope.jsonrpc != "2.0" || envelope.method.is_empty() {
+        return Err((id, Failure::new(INVALID_REQUEST, "invalid request")));
+    }
+    Ok((envelope.method, id))
+}
+
+fn params<P: Default + for<'de> Deserialize<'de>>(line: &[u8]) -> Result<P, Failure> {
+    match serde_json_core::from_slice::<Params<P>>(line) {
+        Ok((Params { params }, _)) => Ok(params),
+        Err(_) => Err(Failure::new(INVALID_PARAMS, "invalid params")),
+    }
+}
+
+/// Run a method, its parameters are read from the request `line`.
+async fn call<'b, D: Device>(
+    device: &'b mut D,
+    method: &str,
+    line: &[u8],
+    data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> Result<Reply<'b>, Failure> {
+    let transfer: Transfer;
+    let body = match method {
+        "nop" => Body::nop(QEmpty {}),
+        "control" => {
+            let control: Control = params(line)?;
+            Body::control(QControl {
+                state_1v2: control.state_1v2,
+                pwm1: control.pwm1,
+                pwm2: control.pwm2,
+                permille: control.permille,
+            })
+        }
+        "status" => Body::status(QEmpty {}),
+        "reset" => Body::reset(QEmpty {}),
+        "shutdown" => Body::shutdown(QEmpty {}),
+        "sensors" => Body::sensors(QEmpty {}),
+        "i2c_scan" => Body::i2c_scan(QEmpty {}),
+        "i2c_read" | "i2c_write" => {
+            transfer = params(line)?;
+            let transfer = QI2cTransfer {
+                address: transfer.address,
+                reg: transfer.reg,
+                length: transfer.length,
+                data: &transfer.data,
+            };
+            match method {
+                "i2c_read" => Body::i2c_read(transfer),
+                _ => Body::i2c_write(transfer),
+            }
+        }
+        "bring_up" => Body::bring_up(QBringUp {
+            key: params::<BringUp>(line)?.key,
+        }),
+        "pwm_config" => {
+            let config: PwmConfig = params(line)?;
+            Body::pwm_config(QPwmConfig {
+                frequency: config.frequency,
+                invert1: config.invert1,
+                invert2: config.invert2,
+                min_duty1: config.min_duty1,
+                min_duty2: config.min_duty2,
+                kick_duty: config.kick_duty,
+                kick_ms: config.kick_ms,
+                ramp_rate: config.ramp_rate,
+                shutdown_on: config.shutdown_on,
+                full_fans_on: config.full_fans_on,
+            })
+        }
+        "reset_info" => Body::reset_info(QEmpty {}),
+        "history" => {
+            let history: History = params(line)?;
+            Body::history(QHistoryRequest {
+                start: history.start,
+                // a page has to fit into a response line
+                count: match history.count {
+                    0 => HISTORY_PAGE,
+                    count => count.min(HISTORY_PAGE),
+                },
+            })
+        }
+        "get_pwm_config" => Body::get_pwm_config(QEmpty {}),
+        // events, logs and states are streamed as protobuf frames, which would
+        // end up between the response lines
+        "subscribe" => return Err(Failure::new(METHOD_NOT_FOUND, "streams need protobuf")),
+        "log_level" => return Err(Failure::new(METHOD_NOT_FOUND, "logs need protobuf")),
+        "batch" => return Err(Failure::new(METHOD_NOT_FOUND, "batches need protobuf")),
+        _ => return Err(Failure::new(METHOD_NOT_FOUND, "method not found")),
+    };
+    Ok(run_command(device, body, data).await?)
+}
+
+fn write<T: Serialize>(out: &mut Output, message: &T) -> Result<(), ()> {
+    out.clear();
+    let _ = out.resize_default(MAX_OUTPUT);
+    // keep room for the line end
+    match serde_json_core::to_slice(message, &mut out[..MAX_OUTPUT - 1]) {
+        Ok(len) => {
+            out.truncate(len);
+            let _ = out.push(b'\n');
+            Ok(())
+        }
+        Err(_) => {
+            out.clear();
+            Err(())
+        }
+    }
+}
+
+fn success<R: Serialize>(out: &mut Output, result: R, id: Id) -> Result<(), ()> {
+    let response = Success {
+        jsonrpc: "2.0",
+        result,
+        id,
+    };
+    write(out, &response)
+}
+
+fn write_reply(out: &mut Output, reply: &Reply, id: Id) -> Result<(), ()> {
+    match reply {
+        Reply::status(state) => success(out, state, id),
+        Reply::sensors(sensors) => success(out, sensors, id),
+        Reply::i2c_scan(scan) => success(out, scan, id),
+        Reply::i2c_read(transfer) => success(out, transfer, id),
+        Reply::reset_info(info) => success(out, info, id),
+        Reply::history(history) => success(out, history, id),
+        Reply::get_pwm_config(config) => success(out, config, id),
+        Reply::None => success(out, (), id),
+        // batches are rejected by `call`
+        Reply::batch(_) => Err(()),
+    }
+}
+
+fn error_response(id: Option<Id>, error: Failure) -> ErrorResponse {
+    ErrorResponse {
+        jsonrpc: "2.0",
+        error,
+        id,
+    }
+}
+
+fn write_error(out: &mut Output, id: Option<Id>, failure: Failure) {
+    // an error response always fits
+    let _ = write(out, &error_response(id, failure));
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+#[cfg(feature = "sim")]
+extern crate alloc;
+
+pub mod events;
+pub mod framing;
+pub mod history;
+pub mod jsonrpc;
+pub mod protobuf;
+pub mod relay;
+pub mod rpc;
+pub mod shell;
+#[cfg(feature = "sim")]
+pub mod sim;
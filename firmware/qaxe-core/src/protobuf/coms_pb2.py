@@ -0,0 +1,2001 @@
+# -*- coding: utf-8 -*-
+# Generated by the protocol buffer compiler.  DO NOT EDIT!
+# source: coms.proto
+
+from google.protobuf import descriptor as _descriptor
+from google.protobuf import message as _message
+from google.protobuf import reflection as _reflection
+from google.protobuf import symbol_database as _symbol_database
+# @@protoc_insertion_point(imports)
+
+_sym_db = _symbol_database.Default()
+
+
+
+
+DESCRIPTOR = _descriptor.FileDescriptor(
+  name='coms.proto',
+  package='',
+  syntax='proto3',
+  serialized_options=None,
+  create_key=_descriptor._internal_create_key,
+  serialized_pb=b'\n\ncoms.proto\"\xc6\x04\n\x08QRequest\x12\n\n\x02id\x18\x01 \x01(\x05\x12\n\n\x02op\x18\x02 \x01(\x05\x12\x0c\n\x04\x64\x61ta\x18\x03 \x01(\x0c\x12\x16\n\x03nop\x18\x10 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1c\n\x07\x63ontrol\x18\x11 \x01(\x0b\x32\t.QControlH\x00\x12\x19\n\x06status\x18\x12 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x18\n\x05reset\x18\x13 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1b\n\x08shutdown\x18\x14 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1a\n\x07sensors\x18\x15 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1b\n\x08i2c_scan\x18\x16 \x01(\x0b\x32\x07.QEmptyH\x00\x12!\n\x08i2c_read\x18\x17 \x01(\x0b\x32\r.QI2cTransferH\x00\x12\"\n\ti2c_write\x18\x18 \x01(\x0b\x32\r.QI2cTransferH\x00\x12\x1d\n\x08\x62ring_up\x18\x19 \x01(\x0b\x32\t.QBringUpH\x00\x12!\n\npwm_config\x18\x1a \x01(\x0b\x32\x0b.QPwmConfigH\x00\x12\x1d\n\nreset_info\x18\x1b \x01(\x0b\x32\x07.QEmptyH\x00\x12 \n\tsubscribe\x18\x1c \x01(\x0b\x32\x0b.QSubscribeH\x00\x12#\n\x07history\x18\x1d \x01(\x0b\x32\x10.QHistoryRequestH\x00\x12\x1f\n\tlog_level\x18\x1e \x01(\x0b\x32\n.QLogLevelH\x00\x12\x18\n\x05\x62\x61tch\x18\x1f \x01(\x0b\x32\x07.QBatchH\x00\x12!\n\x0eget_pwm_config\x18  \x01(\x0b\x32\x07.QEmptyH\x00\x42\x06\n\x04\x62ody\"\x9b\x03\n\tQResponse\x12\n\n\x02id\x18\x01 \x01(\x05\x12\r\n\x05\x65rror\x18\x02 \x01(\x05\x12\x0c\n\x04\x64\x61ta\x18\x03 \x01(\x0c\x12\x17\n\x06\x64\x65tail\x18\x04 \x01(\x0b\x32\x07.QError\x12\x16\n\x05\x65vent\x18\x05 \x01(\x0b\x32\x07.QEvent\x12\x16\n\x05state\x18\x06 \x01(\x0b\x32\x07.QState\x12\x12\n\x03log\x18\x07 \x01(\x0b\x32\x05.QLog\x12\x19\n\x06status\x18\x12 \x01(\x0b\x32\x07.QStateH\x00\x12\x1c\n\x07sensors\x18\x15 \x01(\x0b\x32\t.QSensorsH\x00\x12\x1d\n\x08i2c_scan\x18\x16 \x01(\x0b\x32\t.QI2cScanH\x00\x12!\n\x08i2c_read\x18\x17 \x01(\x0b\x32\r.QI2cTransferH\x00\x12!\n\nreset_info\x18\x1b \x01(\x0b\x32\x0b.QResetInfoH\x00\x12\x1c\n\x07history\x18\x1d \x01(\x0b\x32\t.QHistoryH\x00\x12\x1d\n\x05\x62\x61tch\x18\x1f \x01(\x0b\x32\x0c.QBatchReplyH\x00\x12%\n\x0eget_pwm_config\x18  \x01(\x0b\x32\x0b.QPwmConfigH\x00\x42\x06\n\x04\x62ody\"\x08\n\x06QEmpty\"5\n\x06QError\x12\x0c\n\x04\x63ode\x18\x01 \x01(\x05\x12\x0e\n\x06reason\x18\x02 \x01(\t\x12\r\n\x05\x66ield\x18\x03 \x01(\t\"K\n\x08QControl\x12\x11\n\tstate_1v2\x18\x01 \x01(\x05\x12\x0c\n\x04pwm1\x18\x02 \x01(\x05\x12\x0c\n\x04pwm2\x18\x03 \x01(\x05\x12\x10\n\x08permille\x18\x04 \x01(\x08\"\xb5\x02\n\x06QState\x12\x11\n\tpgood_1v2\x18\x01 \x01(\x05\x12\r\n\x05temp1\x18\x02 \x01(\x05\x12\r\n\x05temp2\x18\x03 \x01(\x05\x12\x0e\n\x06vin_mv\x18\x04 \x01(\x05\x12\x0f\n\x07v1v2_mv\x18\x05 \x01(\x05\x12\x0f\n\x07vdda_mv\x18\x06 \x01(\x05\x12\x10\n\x08mcu_temp\x18\x07 \x01(\x05\x12\x0e\n\x06\x66\x61ults\x18\x08 \x01(\x05\x12\x16\n\x0esensors_failed\x18\t \x01(\x05\x12\x14\n\x0cpwm1_applied\x18\n \x01(\x05\x12\x14\n\x0cpwm2_applied\x18\x0b \x01(\x05\x12\x13\n\x0bpwm1_target\x18\x0c \x01(\x05\x12\x13\n\x0bpwm2_target\x18\r \x01(\x05\x12\x10\n\x08\x66\x61n1_rpm\x18\x0e \x01(\x05\x12\x10\n\x08\x66\x61n2_rpm\x18\x0f \x01(\x05\x12\x14\n\x0ctimestamp_us\x18\x10 \x01(\x04\"\x90\x01\n\x07QSensor\x12\x0f\n\x07\x61\x64\x64ress\x18\x01 \x01(\x05\x12\x0c\n\x04kind\x18\x02 \x01(\x05\x12\x0c\n\x04temp\x18\x03 \x01(\x05\x12\x12\n\nvoltage_mv\x18\x04 \x01(\x05\x12\x12\n\ncurrent_ma\x18\x05 \x01(\x05\x12\x10\n\x08power_mw\x18\x06 \x01(\x05\x12\x0e\n\x06health\x18\x07 \x01(\x05\x12\x0e\n\x06\x65rrors\x18\x08 \x01(\x05\"%\n\x08QSensors\x12\x19\n\x07sensors\x18\x01 \x03(\x0b\x32\x08.QSensor\"\x1d\n\x08QI2cScan\x12\x11\n\taddresses\x18\x01 \x01(\x0c\"J\n\x0cQI2cTransfer\x12\x0f\n\x07\x61\x64\x64ress\x18\x01 \x01(\x05\x12\x0b\n\x03reg\x18\x02 \x01(\x05\x12\x0e\n\x06length\x18\x03 \x01(\x05\x12\x0c\n\x04\x64\x61ta\x18\x04 \x01(\x0c\"\x17\n\x08QBringUp\x12\x0b\n\x03key\x18\x01 \x01(\x05\"\xc9\x01\n\nQPwmConfig\x12\x11\n\tfrequency\x18\x01 \x01(\x05\x12\x0f\n\x07invert1\x18\x02 \x01(\x08\x12\x0f\n\x07invert2\x18\x03 \x01(\x08\x12\x11\n\tmin_duty1\x18\x04 \x01(\x05\x12\x11\n\tmin_duty2\x18\x05 \x01(\x05\x12\x11\n\tkick_duty\x18\x06 \x01(\x05\x12\x0f\n\x07kick_ms\x18\x07 \x01(\x05\x12\x11\n\tramp_rate\x18\x08 \x01(\x05\x12\x13\n\x0bshutdown_on\x18\t \x01(\x05\x12\x14\n\x0c\x66ull_fans_on\x18\n \x01(\x05\"9\n\nQResetInfo\x12\r\n\x05\x63\x61use\x18\x01 \x01(\x05\x12\r\n\x05\x62oots\x18\x02 \x01(\x05\x12\r\n\x05panic\x18\x03 \x01(\t\"E\n\x06QEvent\x12\x0b\n\x03seq\x18\x01 \x01(\x05\x12\x11\n\ttimestamp\x18\x02 \x01(\x05\x12\x0c\n\x04kind\x18\x03 \x01(\x05\x12\r\n\x05value\x18\x04 \x01(\x05\"7\n\nQSubscribe\x12\x0e\n\x06\x65vents\x18\x01 \x01(\x05\x12\x19\n\x11state_interval_ms\x18\x02 \x01(\x05\"/\n\x0fQHistoryRequest\x12\r\n\x05start\x18\x01 \x01(\x05\x12\r\n\x05\x63ount\x18\x02 \x01(\x05\"\xf9\x01\n\x10QHistoryInterval\x12\x0b\n\x03seq\x18\x01 \x01(\x05\x12\x0b\n\x03\x65nd\x18\x02 \x01(\x05\x12\x0f\n\x07samples\x18\x03 \x01(\x05\x12\x11\n\ttemp1_min\x18\x04 \x01(\x05\x12\x11\n\ttemp2_min\x18\x05 \x01(\x05\x12\x11\n\ttemp1_max\x18\x06 \x01(\x05\x12\x11\n\ttemp2_max\x18\x07 \x01(\x05\x12\x11\n\ttemp1_avg\x18\x08 \x01(\x05\x12\x11\n\ttemp2_avg\x18\t \x01(\x05\x12\x10\n\x08pwm1_avg\x18\n \x01(\x05\x12\x10\n\x08pwm2_avg\x18\x0b \x01(\x05\x12\x14\n\x0cpgood_losses\x18\x0c \x01(\x05\x12\x0e\n\x06\x66\x61ults\x18\r \x01(\x05\">\n\x08QHistory\x12$\n\tintervals\x18\x01 \x03(\x0b\x32\x11.QHistoryInterval\x12\x0c\n\x04next\x18\x02 \x01(\x05\"\x1a\n\tQLogLevel\x12\r\n\x05level\x18\x01 \x01(\x05\"%\n\x04QLog\x12\x0c\n\x04\x64\x61ta\x18\x01 \x01(\x0c\x12\x0f\n\x07\x64ropped\x18\x02 \x01(\x05\"2\n\x06QBatch\x12\x18\n\x03ops\x18\x01 \x03(\x0b\x32\x0b.QOperation\x12\x0e\n\x06\x61tomic\x18\x02 \x01(\x08\"\xe3\x03\n\nQOperation\x12\x16\n\x03nop\x18\x10 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1c\n\x07\x63ontrol\x18\x11 \x01(\x0b\x32\t.QControlH\x00\x12\x19\n\x06status\x18\x12 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x18\n\x05reset\x18\x13 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1b\n\x08shutdown\x18\x14 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1a\n\x07sensors\x18\x15 \x01(\x0b\x32\x07.QEmptyH\x00\x12\x1b\n\x08i2c_scan\x18\x16 \x01(\x0b\x32\x07.QEmptyH\x00\x12!\n\x08i2c_read\x18\x17 \x01(\x0b\x32\r.QI2cTransferH\x00\x12\"\n\ti2c_write\x18\x18 \x01(\x0b\x32\r.QI2cTransferH\x00\x12\x1d\n\x08\x62ring_up\x18\x19 \x01(\x0b\x32\t.QBringUpH\x00\x12!\n\npwm_config\x18\x1a \x01(\x0b\x32\x0b.QPwmConfigH\x00\x12\x1d\n\nreset_info\x18\x1b \x01(\x0b\x32\x07.QEmptyH\x00\x12 \n\tsubscribe\x18\x1c \x01(\x0b\x32\x0b.QSubscribeH\x00\x12\x1f\n\tlog_level\x18\x1e \x01(\x0b\x32\n.QLogLevelH\x00\x12!\n\x0eget_pwm_config\x18  \x01(\x0b\x32\x07.QEmptyH\x00\x42\x06\n\x04\x62ody\"(\n\x0bQBatchReply\x12\x19\n\x07results\x18\x01 \x03(\x0b\x32\x08.QResult\"\xfe\x01\n\x07QResult\x12\r\n\x05\x65rror\x18\x02 \x01(\x05\x12\x17\n\x06\x64\x65tail\x18\x04 \x01(\x0b\x32\x07.QError\x12\x19\n\x06status\x18\x12 \x01(\x0b\x32\x07.QStateH\x00\x12\x1c\n\x07sensors\x18\x15 \x01(\x0b\x32\t.QSensorsH\x00\x12\x1d\n\x08i2c_scan\x18\x16 \x01(\x0b\x32\t.QI2cScanH\x00\x12!\n\x08i2c_read\x18\x17 \x01(\x0b\x32\r.QI2cTransferH\x00\x12!\n\nreset_info\x18\x1b \x01(\x0b\x32\x0b.QResetInfoH\x00\x12%\n\x0eget_pwm_config\x18  \x01(\x0b\x32\x0b.QPwmConfigH\x00\x42\x06\n\x04\x62odyb\x06proto3'
+)
+
+
+
+
+_QREQUEST = _descriptor.Descriptor(
+  name='QRequest',
+  full_name='QRequest',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='id', full_name='QRequest.id', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='op', full_name='QRequest.op', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='data', full_name='QRequest.data', index=2,
+      number=3, type=12, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"",
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='nop', full_name='QRequest.nop', index=3,
+      number=16, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='control', full_name='QRequest.control', index=4,
+      number=17, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='status', full_name='QRequest.status', index=5,
+      number=18, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset', full_name='QRequest.reset', index=6,
+      number=19, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='shutdown', full_name='QRequest.shutdown', index=7,
+      number=20, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='sensors', full_name='QRequest.sensors', index=8,
+      number=21, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_scan', full_name='QRequest.i2c_scan', index=9,
+      number=22, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_read', full_name='QRequest.i2c_read', index=10,
+      number=23, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_write', full_name='QRequest.i2c_write', index=11,
+      number=24, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='bring_up', full_name='QRequest.bring_up', index=12,
+      number=25, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm_config', full_name='QRequest.pwm_config', index=13,
+      number=26, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset_info', full_name='QRequest.reset_info', index=14,
+      number=27, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='subscribe', full_name='QRequest.subscribe', index=15,
+      number=28, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='history', full_name='QRequest.history', index=16,
+      number=29, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='log_level', full_name='QRequest.log_level', index=17,
+      number=30, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='batch', full_name='QRequest.batch', index=18,
+      number=31, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='get_pwm_config', full_name='QRequest.get_pwm_config', index=19,
+      number=32, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+    _descriptor.OneofDescriptor(
+      name='body', full_name='QRequest.body',
+      index=0, containing_type=None,
+      create_key=_descriptor._internal_create_key,
+    fields=[]),
+  ],
+  serialized_start=15,
+  serialized_end=597,
+)
+
+
+_QRESPONSE = _descriptor.Descriptor(
+  name='QResponse',
+  full_name='QResponse',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='id', full_name='QResponse.id', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='error', full_name='QResponse.error', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='data', full_name='QResponse.data', index=2,
+      number=3, type=12, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"",
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='detail', full_name='QResponse.detail', index=3,
+      number=4, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='event', full_name='QResponse.event', index=4,
+      number=5, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='state', full_name='QResponse.state', index=5,
+      number=6, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='log', full_name='QResponse.log', index=6,
+      number=7, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='status', full_name='QResponse.status', index=7,
+      number=18, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='sensors', full_name='QResponse.sensors', index=8,
+      number=21, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_scan', full_name='QResponse.i2c_scan', index=9,
+      number=22, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_read', full_name='QResponse.i2c_read', index=10,
+      number=23, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset_info', full_name='QResponse.reset_info', index=11,
+      number=27, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='history', full_name='QResponse.history', index=12,
+      number=29, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='batch', full_name='QResponse.batch', index=13,
+      number=31, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='get_pwm_config', full_name='QResponse.get_pwm_config', index=14,
+      number=32, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+    _descriptor.OneofDescriptor(
+      name='body', full_name='QResponse.body',
+      index=0, containing_type=None,
+      create_key=_descriptor._internal_create_key,
+    fields=[]),
+  ],
+  serialized_start=600,
+  serialized_end=1011,
+)
+
+
+_QEMPTY = _descriptor.Descriptor(
+  name='QEmpty',
+  full_name='QEmpty',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1013,
+  serialized_end=1021,
+)
+
+
+_QERROR = _descriptor.Descriptor(
+  name='QError',
+  full_name='QError',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='code', full_name='QError.code', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reason', full_name='QError.reason', index=1,
+      number=2, type=9, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"".decode('utf-8'),
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='field', full_name='QError.field', index=2,
+      number=3, type=9, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"".decode('utf-8'),
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1023,
+  serialized_end=1076,
+)
+
+
+_QCONTROL = _descriptor.Descriptor(
+  name='QControl',
+  full_name='QControl',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='state_1v2', full_name='QControl.state_1v2', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm1', full_name='QControl.pwm1', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm2', full_name='QControl.pwm2', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='permille', full_name='QControl.permille', index=3,
+      number=4, type=8, cpp_type=7, label=1,
+      has_default_value=False, default_value=False,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1078,
+  serialized_end=1153,
+)
+
+
+_QSTATE = _descriptor.Descriptor(
+  name='QState',
+  full_name='QState',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='pgood_1v2', full_name='QState.pgood_1v2', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp1', full_name='QState.temp1', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp2', full_name='QState.temp2', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='vin_mv', full_name='QState.vin_mv', index=3,
+      number=4, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='v1v2_mv', full_name='QState.v1v2_mv', index=4,
+      number=5, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='vdda_mv', full_name='QState.vdda_mv', index=5,
+      number=6, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='mcu_temp', full_name='QState.mcu_temp', index=6,
+      number=7, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='faults', full_name='QState.faults', index=7,
+      number=8, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='sensors_failed', full_name='QState.sensors_failed', index=8,
+      number=9, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm1_applied', full_name='QState.pwm1_applied', index=9,
+      number=10, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm2_applied', full_name='QState.pwm2_applied', index=10,
+      number=11, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm1_target', full_name='QState.pwm1_target', index=11,
+      number=12, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm2_target', full_name='QState.pwm2_target', index=12,
+      number=13, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='fan1_rpm', full_name='QState.fan1_rpm', index=13,
+      number=14, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='fan2_rpm', full_name='QState.fan2_rpm', index=14,
+      number=15, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='timestamp_us', full_name='QState.timestamp_us', index=15,
+      number=16, type=4, cpp_type=4, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1156,
+  serialized_end=1465,
+)
+
+
+_QSENSOR = _descriptor.Descriptor(
+  name='QSensor',
+  full_name='QSensor',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='address', full_name='QSensor.address', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='kind', full_name='QSensor.kind', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp', full_name='QSensor.temp', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='voltage_mv', full_name='QSensor.voltage_mv', index=3,
+      number=4, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='current_ma', full_name='QSensor.current_ma', index=4,
+      number=5, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='power_mw', full_name='QSensor.power_mw', index=5,
+      number=6, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='health', full_name='QSensor.health', index=6,
+      number=7, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='errors', full_name='QSensor.errors', index=7,
+      number=8, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1468,
+  serialized_end=1612,
+)
+
+
+_QSENSORS = _descriptor.Descriptor(
+  name='QSensors',
+  full_name='QSensors',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='sensors', full_name='QSensors.sensors', index=0,
+      number=1, type=11, cpp_type=10, label=3,
+      has_default_value=False, default_value=[],
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1614,
+  serialized_end=1651,
+)
+
+
+_QI2CSCAN = _descriptor.Descriptor(
+  name='QI2cScan',
+  full_name='QI2cScan',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='addresses', full_name='QI2cScan.addresses', index=0,
+      number=1, type=12, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"",
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1653,
+  serialized_end=1682,
+)
+
+
+_QI2CTRANSFER = _descriptor.Descriptor(
+  name='QI2cTransfer',
+  full_name='QI2cTransfer',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='address', full_name='QI2cTransfer.address', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reg', full_name='QI2cTransfer.reg', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='length', full_name='QI2cTransfer.length', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='data', full_name='QI2cTransfer.data', index=3,
+      number=4, type=12, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"",
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1684,
+  serialized_end=1758,
+)
+
+
+_QBRINGUP = _descriptor.Descriptor(
+  name='QBringUp',
+  full_name='QBringUp',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='key', full_name='QBringUp.key', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1760,
+  serialized_end=1783,
+)
+
+
+_QPWMCONFIG = _descriptor.Descriptor(
+  name='QPwmConfig',
+  full_name='QPwmConfig',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='frequency', full_name='QPwmConfig.frequency', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='invert1', full_name='QPwmConfig.invert1', index=1,
+      number=2, type=8, cpp_type=7, label=1,
+      has_default_value=False, default_value=False,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='invert2', full_name='QPwmConfig.invert2', index=2,
+      number=3, type=8, cpp_type=7, label=1,
+      has_default_value=False, default_value=False,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='min_duty1', full_name='QPwmConfig.min_duty1', index=3,
+      number=4, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='min_duty2', full_name='QPwmConfig.min_duty2', index=4,
+      number=5, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='kick_duty', full_name='QPwmConfig.kick_duty', index=5,
+      number=6, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='kick_ms', full_name='QPwmConfig.kick_ms', index=6,
+      number=7, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='ramp_rate', full_name='QPwmConfig.ramp_rate', index=7,
+      number=8, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='shutdown_on', full_name='QPwmConfig.shutdown_on', index=8,
+      number=9, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='full_fans_on', full_name='QPwmConfig.full_fans_on', index=9,
+      number=10, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1786,
+  serialized_end=1987,
+)
+
+
+_QRESETINFO = _descriptor.Descriptor(
+  name='QResetInfo',
+  full_name='QResetInfo',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='cause', full_name='QResetInfo.cause', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='boots', full_name='QResetInfo.boots', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='panic', full_name='QResetInfo.panic', index=2,
+      number=3, type=9, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"".decode('utf-8'),
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=1989,
+  serialized_end=2046,
+)
+
+
+_QEVENT = _descriptor.Descriptor(
+  name='QEvent',
+  full_name='QEvent',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='seq', full_name='QEvent.seq', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='timestamp', full_name='QEvent.timestamp', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='kind', full_name='QEvent.kind', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='value', full_name='QEvent.value', index=3,
+      number=4, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2048,
+  serialized_end=2117,
+)
+
+
+_QSUBSCRIBE = _descriptor.Descriptor(
+  name='QSubscribe',
+  full_name='QSubscribe',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='events', full_name='QSubscribe.events', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='state_interval_ms', full_name='QSubscribe.state_interval_ms', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2119,
+  serialized_end=2174,
+)
+
+
+_QHISTORYREQUEST = _descriptor.Descriptor(
+  name='QHistoryRequest',
+  full_name='QHistoryRequest',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='start', full_name='QHistoryRequest.start', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='count', full_name='QHistoryRequest.count', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2176,
+  serialized_end=2223,
+)
+
+
+_QHISTORYINTERVAL = _descriptor.Descriptor(
+  name='QHistoryInterval',
+  full_name='QHistoryInterval',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='seq', full_name='QHistoryInterval.seq', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='end', full_name='QHistoryInterval.end', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='samples', full_name='QHistoryInterval.samples', index=2,
+      number=3, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp1_min', full_name='QHistoryInterval.temp1_min', index=3,
+      number=4, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp2_min', full_name='QHistoryInterval.temp2_min', index=4,
+      number=5, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp1_max', full_name='QHistoryInterval.temp1_max', index=5,
+      number=6, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp2_max', full_name='QHistoryInterval.temp2_max', index=6,
+      number=7, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp1_avg', full_name='QHistoryInterval.temp1_avg', index=7,
+      number=8, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='temp2_avg', full_name='QHistoryInterval.temp2_avg', index=8,
+      number=9, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm1_avg', full_name='QHistoryInterval.pwm1_avg', index=9,
+      number=10, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm2_avg', full_name='QHistoryInterval.pwm2_avg', index=10,
+      number=11, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pgood_losses', full_name='QHistoryInterval.pgood_losses', index=11,
+      number=12, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='faults', full_name='QHistoryInterval.faults', index=12,
+      number=13, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2226,
+  serialized_end=2475,
+)
+
+
+_QHISTORY = _descriptor.Descriptor(
+  name='QHistory',
+  full_name='QHistory',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='intervals', full_name='QHistory.intervals', index=0,
+      number=1, type=11, cpp_type=10, label=3,
+      has_default_value=False, default_value=[],
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='next', full_name='QHistory.next', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2477,
+  serialized_end=2539,
+)
+
+
+_QLOGLEVEL = _descriptor.Descriptor(
+  name='QLogLevel',
+  full_name='QLogLevel',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='level', full_name='QLogLevel.level', index=0,
+      number=1, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2541,
+  serialized_end=2567,
+)
+
+
+_QLOG = _descriptor.Descriptor(
+  name='QLog',
+  full_name='QLog',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='data', full_name='QLog.data', index=0,
+      number=1, type=12, cpp_type=9, label=1,
+      has_default_value=False, default_value=b"",
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='dropped', full_name='QLog.dropped', index=1,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2569,
+  serialized_end=2606,
+)
+
+
+_QBATCH = _descriptor.Descriptor(
+  name='QBatch',
+  full_name='QBatch',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='ops', full_name='QBatch.ops', index=0,
+      number=1, type=11, cpp_type=10, label=3,
+      has_default_value=False, default_value=[],
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='atomic', full_name='QBatch.atomic', index=1,
+      number=2, type=8, cpp_type=7, label=1,
+      has_default_value=False, default_value=False,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=2608,
+  serialized_end=2658,
+)
+
+
+_QOPERATION = _descriptor.Descriptor(
+  name='QOperation',
+  full_name='QOperation',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='nop', full_name='QOperation.nop', index=0,
+      number=16, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='control', full_name='QOperation.control', index=1,
+      number=17, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='status', full_name='QOperation.status', index=2,
+      number=18, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset', full_name='QOperation.reset', index=3,
+      number=19, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='shutdown', full_name='QOperation.shutdown', index=4,
+      number=20, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='sensors', full_name='QOperation.sensors', index=5,
+      number=21, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_scan', full_name='QOperation.i2c_scan', index=6,
+      number=22, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_read', full_name='QOperation.i2c_read', index=7,
+      number=23, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_write', full_name='QOperation.i2c_write', index=8,
+      number=24, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='bring_up', full_name='QOperation.bring_up', index=9,
+      number=25, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='pwm_config', full_name='QOperation.pwm_config', index=10,
+      number=26, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset_info', full_name='QOperation.reset_info', index=11,
+      number=27, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='subscribe', full_name='QOperation.subscribe', index=12,
+      number=28, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='log_level', full_name='QOperation.log_level', index=13,
+      number=30, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='get_pwm_config', full_name='QOperation.get_pwm_config', index=14,
+      number=32, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+    _descriptor.OneofDescriptor(
+      name='body', full_name='QOperation.body',
+      index=0, containing_type=None,
+      create_key=_descriptor._internal_create_key,
+    fields=[]),
+  ],
+  serialized_start=2661,
+  serialized_end=3144,
+)
+
+
+_QBATCHREPLY = _descriptor.Descriptor(
+  name='QBatchReply',
+  full_name='QBatchReply',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='results', full_name='QBatchReply.results', index=0,
+      number=1, type=11, cpp_type=10, label=3,
+      has_default_value=False, default_value=[],
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+  ],
+  serialized_start=3146,
+  serialized_end=3186,
+)
+
+
+_QRESULT = _descriptor.Descriptor(
+  name='QResult',
+  full_name='QResult',
+  filename=None,
+  file=DESCRIPTOR,
+  containing_type=None,
+  create_key=_descriptor._internal_create_key,
+  fields=[
+    _descriptor.FieldDescriptor(
+      name='error', full_name='QResult.error', index=0,
+      number=2, type=5, cpp_type=1, label=1,
+      has_default_value=False, default_value=0,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='detail', full_name='QResult.detail', index=1,
+      number=4, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='status', full_name='QResult.status', index=2,
+      number=18, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='sensors', full_name='QResult.sensors', index=3,
+      number=21, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_scan', full_name='QResult.i2c_scan', index=4,
+      number=22, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='i2c_read', full_name='QResult.i2c_read', index=5,
+      number=23, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='reset_info', full_name='QResult.reset_info', index=6,
+      number=27, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+    _descriptor.FieldDescriptor(
+      name='get_pwm_config', full_name='QResult.get_pwm_config', index=7,
+      number=32, type=11, cpp_type=10, label=1,
+      has_default_value=False, default_value=None,
+      message_type=None, enum_type=None, containing_type=None,
+      is_extension=False, extension_scope=None,
+      serialized_options=None, file=DESCRIPTOR,  create_key=_descriptor._internal_create_key),
+  ],
+  extensions=[
+  ],
+  nested_types=[],
+  enum_types=[
+  ],
+  serialized_options=None,
+  is_extendable=False,
+  syntax='proto3',
+  extension_ranges=[],
+  oneofs=[
+    _descriptor.OneofDescriptor(
+      name='body', full_name='QResult.body',
+      index=0, containing_type=None,
+      create_key=_descriptor._internal_create_key,
+    fields=[]),
+  ],
+  serialized_start=3189,
+  serialized_end=3443,
+)
+
+_QREQUEST.fields_by_name['nop'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['nop'])
+_QREQUEST.fields_by_name['nop'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['control'].message_type = _QCONTROL
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['control'])
+_QREQUEST.fields_by_name['control'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['status'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['status'])
+_QREQUEST.fields_by_name['status'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['reset'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['reset'])
+_QREQUEST.fields_by_name['reset'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['shutdown'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['shutdown'])
+_QREQUEST.fields_by_name['shutdown'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['sensors'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['sensors'])
+_QREQUEST.fields_by_name['sensors'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['i2c_scan'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['i2c_scan'])
+_QREQUEST.fields_by_name['i2c_scan'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['i2c_read'].message_type = _QI2CTRANSFER
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['i2c_read'])
+_QREQUEST.fields_by_name['i2c_read'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['i2c_write'].message_type = _QI2CTRANSFER
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['i2c_write'])
+_QREQUEST.fields_by_name['i2c_write'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['bring_up'].message_type = _QBRINGUP
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['bring_up'])
+_QREQUEST.fields_by_name['bring_up'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['pwm_config'].message_type = _QPWMCONFIG
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['pwm_config'])
+_QREQUEST.fields_by_name['pwm_config'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['reset_info'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['reset_info'])
+_QREQUEST.fields_by_name['reset_info'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['subscribe'].message_type = _QSUBSCRIBE
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['subscribe'])
+_QREQUEST.fields_by_name['subscribe'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['history'].message_type = _QHISTORYREQUEST
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['history'])
+_QREQUEST.fields_by_name['history'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['log_level'].message_type = _QLOGLEVEL
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['log_level'])
+_QREQUEST.fields_by_name['log_level'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['batch'].message_type = _QBATCH
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['batch'])
+_QREQUEST.fields_by_name['batch'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QREQUEST.fields_by_name['get_pwm_config'].message_type = _QEMPTY
+_QREQUEST.oneofs_by_name['body'].fields.append(
+  _QREQUEST.fields_by_name['get_pwm_config'])
+_QREQUEST.fields_by_name['get_pwm_config'].containing_oneof = _QREQUEST.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['detail'].message_type = _QERROR
+_QRESPONSE.fields_by_name['event'].message_type = _QEVENT
+_QRESPONSE.fields_by_name['state'].message_type = _QSTATE
+_QRESPONSE.fields_by_name['log'].message_type = _QLOG
+_QRESPONSE.fields_by_name['status'].message_type = _QSTATE
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['status'])
+_QRESPONSE.fields_by_name['status'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['sensors'].message_type = _QSENSORS
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['sensors'])
+_QRESPONSE.fields_by_name['sensors'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['i2c_scan'].message_type = _QI2CSCAN
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['i2c_scan'])
+_QRESPONSE.fields_by_name['i2c_scan'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['i2c_read'].message_type = _QI2CTRANSFER
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['i2c_read'])
+_QRESPONSE.fields_by_name['i2c_read'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['reset_info'].message_type = _QRESETINFO
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['reset_info'])
+_QRESPONSE.fields_by_name['reset_info'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['history'].message_type = _QHISTORY
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['history'])
+_QRESPONSE.fields_by_name['history'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['batch'].message_type = _QBATCHREPLY
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['batch'])
+_QRESPONSE.fields_by_name['batch'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QRESPONSE.fields_by_name['get_pwm_config'].message_type = _QPWMCONFIG
+_QRESPONSE.oneofs_by_name['body'].fields.append(
+  _QRESPONSE.fields_by_name['get_pwm_config'])
+_QRESPONSE.fields_by_name['get_pwm_config'].containing_oneof = _QRESPONSE.oneofs_by_name['body']
+_QSENSORS.fields_by_name['sensors'].message_type = _QSENSOR
+_QHISTORY.fields_by_name['intervals'].message_type = _QHISTORYINTERVAL
+_QBATCH.fields_by_name['ops'].message_type = _QOPERATION
+_QOPERATION.fields_by_name['nop'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['nop'])
+_QOPERATION.fields_by_name['nop'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['control'].message_type = _QCONTROL
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['control'])
+_QOPERATION.fields_by_name['control'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['status'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['status'])
+_QOPERATION.fields_by_name['status'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['reset'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['reset'])
+_QOPERATION.fields_by_name['reset'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['shutdown'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['shutdown'])
+_QOPERATION.fields_by_name['shutdown'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['sensors'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['sensors'])
+_QOPERATION.fields_by_name['sensors'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['i2c_scan'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['i2c_scan'])
+_QOPERATION.fields_by_name['i2c_scan'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['i2c_read'].message_type = _QI2CTRANSFER
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['i2c_read'])
+_QOPERATION.fields_by_name['i2c_read'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['i2c_write'].message_type = _QI2CTRANSFER
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['i2c_write'])
+_QOPERATION.fields_by_name['i2c_write'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['bring_up'].message_type = _QBRINGUP
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['bring_up'])
+_QOPERATION.fields_by_name['bring_up'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['pwm_config'].message_type = _QPWMCONFIG
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['pwm_config'])
+_QOPERATION.fields_by_name['pwm_config'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['reset_info'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['reset_info'])
+_QOPERATION.fields_by_name['reset_info'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['subscribe'].message_type = _QSUBSCRIBE
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['subscribe'])
+_QOPERATION.fields_by_name['subscribe'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['log_level'].message_type = _QLOGLEVEL
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['log_level'])
+_QOPERATION.fields_by_name['log_level'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QOPERATION.fields_by_name['get_pwm_config'].message_type = _QEMPTY
+_QOPERATION.oneofs_by_name['body'].fields.append(
+  _QOPERATION.fields_by_name['get_pwm_config'])
+_QOPERATION.fields_by_name['get_pwm_config'].containing_oneof = _QOPERATION.oneofs_by_name['body']
+_QBATCHREPLY.fields_by_name['results'].message_type = _QRESULT
+_QRESULT.fields_by_name['detail'].message_type = _QERROR
+_QRESULT.fields_by_name['status'].message_type = _QSTATE
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['status'])
+_QRESULT.fields_by_name['status'].containing_oneof = _QRESULT.oneofs_by_name['body']
+_QRESULT.fields_by_name['sensors'].message_type = _QSENSORS
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['sensors'])
+_QRESULT.fields_by_name['sensors'].containing_oneof = _QRESULT.oneofs_by_name['body']
+_QRESULT.fields_by_name['i2c_scan'].message_type = _QI2CSCAN
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['i2c_scan'])
+_QRESULT.fields_by_name['i2c_scan'].containing_oneof = _QRESULT.oneofs_by_name['body']
+_QRESULT.fields_by_name['i2c_read'].message_type = _QI2CTRANSFER
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['i2c_read'])
+_QRESULT.fields_by_name['i2c_read'].containing_oneof = _QRESULT.oneofs_by_name['body']
+_QRESULT.fields_by_name['reset_info'].message_type = _QRESETINFO
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['reset_info'])
+_QRESULT.fields_by_name['reset_info'].containing_oneof = _QRESULT.oneofs_by_name['body']
+_QRESULT.fields_by_name['get_pwm_config'].message_type = _QPWMCONFIG
+_QRESULT.oneofs_by_name['body'].fields.append(
+  _QRESULT.fields_by_name['get_pwm_config'])
+_QRESULT.fields_by_name['get_pwm_config'].containing_oneof = _QRESULT.oneofs_by_name['body']
+DESCRIPTOR.message_types_by_name['QRequest'] = _QREQUEST
+DESCRIPTOR.message_types_by_name['QResponse'] = _QRESPONSE
+DESCRIPTOR.message_types_by_name['QEmpty'] = _QEMPTY
+DESCRIPTOR.message_types_by_name['QError'] = _QERROR
+DESCRIPTOR.message_types_by_name['QControl'] = _QCONTROL
+DESCRIPTOR.message_types_by_name['QState'] = _QSTATE
+DESCRIPTOR.message_types_by_name['QSensor'] = _QSENSOR
+DESCRIPTOR.message_types_by_name['QSensors'] = _QSENSORS
+DESCRIPTOR.message_types_by_name['QI2cScan'] = _QI2CSCAN
+DESCRIPTOR.message_types_by_name['QI2cTransfer'] = _QI2CTRANSFER
+DESCRIPTOR.message_types_by_name['QBringUp'] = _QBRINGUP
+DESCRIPTOR.message_types_by_name['QPwmConfig'] = _QPWMCONFIG
+DESCRIPTOR.message_types_by_name['QResetInfo'] = _QRESETINFO
+DESCRIPTOR.message_types_by_name['QEvent'] = _QEVENT
+DESCRIPTOR.message_types_by_name['QSubscribe'] = _QSUBSCRIBE
+DESCRIPTOR.message_types_by_name['QHistoryRequest'] = _QHISTORYREQUEST
+DESCRIPTOR.message_types_by_name['QHistoryInterval'] = _QHISTORYINTERVAL
+DESCRIPTOR.message_types_by_name['QHistory'] = _QHISTORY
+DESCRIPTOR.message_types_by_name['QLogLevel'] = _QLOGLEVEL
+DESCRIPTOR.message_types_by_name['QLog'] = _QLOG
+DESCRIPTOR.message_types_by_name['QBatch'] = _QBATCH
+DESCRIPTOR.message_types_by_name['QOperation'] = _QOPERATION
+DESCRIPTOR.message_types_by_name['QBatchReply'] = _QBATCHREPLY
+DESCRIPTOR.message_types_by_name['QResult'] = _QRESULT
+_sym_db.RegisterFileDescriptor(DESCRIPTOR)
+
+QRequest = _reflection.GeneratedProtocolMessageType('QRequest', (_message.Message,), {
+  'DESCRIPTOR' : _QREQUEST,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QRequest)
+  })
+_sym_db.RegisterMessage(QRequest)
+
+QResponse = _reflection.GeneratedProtocolMessageType('QResponse', (_message.Message,), {
+  'DESCRIPTOR' : _QRESPONSE,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QResponse)
+  })
+_sym_db.RegisterMessage(QResponse)
+
+QEmpty = _reflection.GeneratedProtocolMessageType('QEmpty', (_message.Message,), {
+  'DESCRIPTOR' : _QEMPTY,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QEmpty)
+  })
+_sym_db.RegisterMessage(QEmpty)
+
+QError = _reflection.GeneratedProtocolMessageType('QError', (_message.Message,), {
+  'DESCRIPTOR' : _QERROR,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QError)
+  })
+_sym_db.RegisterMessage(QError)
+
+QControl = _reflection.GeneratedProtocolMessageType('QControl', (_message.Message,), {
+  'DESCRIPTOR' : _QCONTROL,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QControl)
+  })
+_sym_db.RegisterMessage(QControl)
+
+QState = _reflection.GeneratedProtocolMessageType('QState', (_message.Message,), {
+  'DESCRIPTOR' : _QSTATE,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QState)
+  })
+_sym_db.RegisterMessage(QState)
+
+QSensor = _reflection.GeneratedProtocolMessageType('QSensor', (_message.Message,), {
+  'DESCRIPTOR' : _QSENSOR,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QSensor)
+  })
+_sym_db.RegisterMessage(QSensor)
+
+QSensors = _reflection.GeneratedProtocolMessageType('QSensors', (_message.Message,), {
+  'DESCRIPTOR' : _QSENSORS,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QSensors)
+  })
+_sym_db.RegisterMessage(QSensors)
+
+QI2cScan = _reflection.GeneratedProtocolMessageType('QI2cScan', (_message.Message,), {
+  'DESCRIPTOR' : _QI2CSCAN,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QI2cScan)
+  })
+_sym_db.RegisterMessage(QI2cScan)
+
+QI2cTransfer = _reflection.GeneratedProtocolMessageType('QI2cTransfer', (_message.Message,), {
+  'DESCRIPTOR' : _QI2CTRANSFER,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QI2cTransfer)
+  })
+_sym_db.RegisterMessage(QI2cTransfer)
+
+QBringUp = _reflection.GeneratedProtocolMessageType('QBringUp', (_message.Message,), {
+  'DESCRIPTOR' : _QBRINGUP,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QBringUp)
+  })
+_sym_db.RegisterMessage(QBringUp)
+
+QPwmConfig = _reflection.GeneratedProtocolMessageType('QPwmConfig', (_message.Message,), {
+  'DESCRIPTOR' : _QPWMCONFIG,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QPwmConfig)
+  })
+_sym_db.RegisterMessage(QPwmConfig)
+
+QResetInfo = _reflection.GeneratedProtocolMessageType('QResetInfo', (_message.Message,), {
+  'DESCRIPTOR' : _QRESETINFO,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QResetInfo)
+  })
+_sym_db.RegisterMessage(QResetInfo)
+
+QEvent = _reflection.GeneratedProtocolMessageType('QEvent', (_message.Message,), {
+  'DESCRIPTOR' : _QEVENT,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QEvent)
+  })
+_sym_db.RegisterMessage(QEvent)
+
+QSubscribe = _reflection.GeneratedProtocolMessageType('QSubscribe', (_message.Message,), {
+  'DESCRIPTOR' : _QSUBSCRIBE,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QSubscribe)
+  })
+_sym_db.RegisterMessage(QSubscribe)
+
+QHistoryRequest = _reflection.GeneratedProtocolMessageType('QHistoryRequest', (_message.Message,), {
+  'DESCRIPTOR' : _QHISTORYREQUEST,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QHistoryRequest)
+  })
+_sym_db.RegisterMessage(QHistoryRequest)
+
+QHistoryInterval = _reflection.GeneratedProtocolMessageType('QHistoryInterval', (_message.Message,), {
+  'DESCRIPTOR' : _QHISTORYINTERVAL,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QHistoryInterval)
+  })
+_sym_db.RegisterMessage(QHistoryInterval)
+
+QHistory = _reflection.GeneratedProtocolMessageType('QHistory', (_message.Message,), {
+  'DESCRIPTOR' : _QHISTORY,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QHistory)
+  })
+_sym_db.RegisterMessage(QHistory)
+
+QLogLevel = _reflection.GeneratedProtocolMessageType('QLogLevel', (_message.Message,), {
+  'DESCRIPTOR' : _QLOGLEVEL,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QLogLevel)
+  })
+_sym_db.RegisterMessage(QLogLevel)
+
+QLog = _reflection.GeneratedProtocolMessageType('QLog', (_message.Message,), {
+  'DESCRIPTOR' : _QLOG,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QLog)
+  })
+_sym_db.RegisterMessage(QLog)
+
+QBatch = _reflection.GeneratedProtocolMessageType('QBatch', (_message.Message,), {
+  'DESCRIPTOR' : _QBATCH,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QBatch)
+  })
+_sym_db.RegisterMessage(QBatch)
+
+QOperation = _reflection.GeneratedProtocolMessageType('QOperation', (_message.Message,), {
+  'DESCRIPTOR' : _QOPERATION,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QOperation)
+  })
+_sym_db.RegisterMessage(QOperation)
+
+QBatchReply = _reflection.GeneratedProtocolMessageType('QBatchReply', (_message.Message,), {
+  'DESCRIPTOR' : _QBATCHREPLY,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QBatchReply)
+  })
+_sym_db.RegisterMessage(QBatchReply)
+
+QResult = _reflection.GeneratedProtocolMessageType('QResult', (_message.Message,), {
+  'DESCRIPTOR' : _QRESULT,
+  '__module__' : 'coms_pb2'
+  # @@protoc_insertion_point(class_scope:QResult)
+  })
+_sym_db.RegisterMessage(QResult)
+
+
+# @@protoc_insertion_point(module_scope)
@@ -0,0 +1,51 @@
+# Typed errors for QResponse.error, keep in sync with Errors in rpc.rs
+
+
+class QaxeError(Exception):
+    code = None
+
+    def __init__(self, code, reason="", field=""):
+        self.code = code
+        self.reason = reason
+        self.field = field
+        detail = reason or "error %d" % code
+        super().__init__("%s (%s)" % (detail, field) if field else detail)
+
+
+class InvalidCommand(QaxeError): pass
+class DeserializationError(QaxeError): pass
+class SerializationError(QaxeError): pass
+class AccessDenied(QaxeError): pass
+class I2cError(QaxeError): pass
+class InvalidParameter(QaxeError): pass
+class OversizedFrame(QaxeError): pass
+class OutOfRange(InvalidParameter): pass
+class Busy(QaxeError): pass
+class Unsupported(QaxeError): pass
+
+
+ERRORS = {
+    1: InvalidCommand,
+    2: DeserializationError,
+    3: SerializationError,
+    4: DeserializationError,
+    5: SerializationError,
+    6: AccessDenied,
+    7: I2cError,
+    8: InvalidParameter,
+    9: OversizedFrame,
+    10: OutOfRange,
+    11: Busy,
+    12: Unsupported,
+}
+
+
+def check(response):
+    """Raise the typed error of a failed QResponse or QResult, return it otherwise."""
+    if response.error == 0:
+        return response
+
+    cls = ERRORS.get(response.error, QaxeError)
+    if response.HasField("detail"):
+        raise cls(response.error, response.detail.reason, response.detail.field)
+    raise cls(response.error)
@@ -0,0 +1,8 @@
+// coms.rs is generated from coms.proto by build.rs, see README.md
+#[allow(non_snake_case, non_camel_case_types, unused_imports, clippy::all)]
+pub mod coms {
+    include!(concat!(env!("OUT_DIR"), "/coms.rs"));
+}
+pub mod wire;
+
+pub use wire::{deserialize_from_slice, serialize_into_slice};
@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+"""Forward the log output of the control channel to stdout for defmt-print.
+
+    python3 usb_log.py /dev/ttyACM1 --level info | defmt-print -e qaxe.elf
+
+The output is the raw defmt stream, it is decoded with the table of the ELF
+that runs on the board.
+"""
+
+import argparse
+import sys
+
+import serial
+from google.protobuf.internal.decoder import _DecodeVarint32
+from google.protobuf.internal.encoder import _VarintBytes
+
+import coms_pb2
+import errors
+
+LEVELS = ["off", "error", "warn", "info", "debug", "trace"]
+
+
+def delimited(message):
+    data = message.SerializeToString()
+    return _VarintBytes(len(data)) + data
+
+
+def frames(port):
+    """Yield the length-delimited frames read from the port."""
+    buf = b""
+    while True:
+        buf += port.read(port.in_waiting or 1)
+        while buf:
+            try:
+                length, pos = _DecodeVarint32(buf, 0)
+            except IndexError:
+                break
+            if len(buf) < pos + length:
+                break
+            yield buf[pos:pos + length]
+            buf = buf[pos + length:]
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("port", help="control channel, e.g. /dev/ttyACM1")
+    parser.add_argument("--level", choices=LEVELS[1:], default="info")
+    args = parser.parse_args()
+
+    port = serial.Serial(args.port, timeout=1)
+    level = coms_pb2.QLogLevel(level=LEVELS.index(args.level))
+    request = coms_pb2.QRequest(id=1, log_level=level)
+    port.write(delimited(request))
+
+    out = sys.stdout.buffer
+    for frame in frames(port):
+        response = coms_pb2.QResponse()
+        response.ParseFromString(frame)
+        if response.HasField("log"):
+            if response.log.dropped:
+                print("%d bytes of log output dropped" % response.log.dropped, file=sys.stderr)
+            out.write(response.log.data)
+            out.flush()
+        elif response.id == request.id:
+            errors.check(response)
+
+
+if __name__ == "__main__":
+    main()
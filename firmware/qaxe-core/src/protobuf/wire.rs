@@ -0,0 +1,287 @@
+//! Protobuf wire format without heap, for the code generated by `qaxe-protogen`.
+//!
+//! Decoded messages borrow their bytes and strings from the input, repeated
+//! fields go into `heapless::Vec`s sized in `coms.options`. Encoding writes
+//! into a fixed buffer. Every read is checked against the end of the message
+//! it belongs to, so malformed input fails with an error instead of a panic.
+
+/// Varint length prefixes and tags are written with at most this many bytes.
+pub const MAX_VARINT: usize = 10;
+
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_FIXED64: u8 = 1;
+const WIRE_TYPE_LENGTH_DELIMITED: u8 = 2;
+const WIRE_TYPE_FIXED32: u8 = 5;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// A varint longer than 10 bytes.
+    Varint,
+    /// A field reaching past the end of its message.
+    UnexpectedEndOfBuffer,
+    /// A string that isn't UTF-8.
+    Utf8,
+    /// Groups and the reserved wire types.
+    UnknownWireType(u8),
+    /// More elements of a repeated field than it has room for.
+    RepeatedFieldFull,
+    /// The message doesn't fit the output buffer.
+    OutputBufferTooSmall,
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+pub trait MessageRead<'a>: Sized {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self>;
+}
+
+pub trait MessageWrite {
+    /// Length of the message without its length prefix.
+    fn get_size(&self) -> usize;
+    fn write_message(&self, w: &mut Writer) -> Result<()>;
+}
+
+pub fn sizeof_varint(value: u64) -> usize {
+    match value {
+        0x0..=0x7f => 1,
+        _ => (64 - value.leading_zeros() as usize).div_ceil(7),
+    }
+}
+
+/// Length of a length-delimited field without its tag.
+pub fn sizeof_len(len: usize) -> usize {
+    sizeof_varint(len as u64) + len
+}
+
+fn zigzag32(value: i32) -> u64 {
+    ((value << 1) ^ (value >> 31)) as u32 as u64
+}
+
+/// `sizeof_varint` for `i32` fields, negative values take 10 bytes.
+pub fn sizeof_int32(value: i32) -> usize {
+    sizeof_varint(value as u64)
+}
+
+pub fn sizeof_sint32(value: i32) -> usize {
+    sizeof_varint(zigzag32(value))
+}
+
+/// Reads the fields of a message between `start` and `end` of a buffer.
+pub struct BytesReader {
+    start: usize,
+    end: usize,
+}
+
+impl BytesReader {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        BytesReader {
+            start: 0,
+            end: bytes.len(),
+        }
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.start >= self.end
+    }
+
+    fn read_u8(&mut self, bytes: &[u8]) -> Result<u8> {
+        if self.start >= self.end {
+            return Err(Error::UnexpectedEndOfBuffer);
+        }
+        let byte = bytes[self.start];
+        self.start += 1;
+        Ok(byte)
+    }
+
+    pub fn read_varint64(&mut self, bytes: &[u8]) -> Result<u64> {
+        let mut value = 0u64;
+        for i in 0..MAX_VARINT {
+            let byte = self.read_u8(bytes)?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::Varint)
+    }
+
+    pub fn next_tag(&mut self, bytes: &[u8]) -> Result<u32> {
+        self.read_varint64(bytes).map(|tag| tag as u32)
+    }
+
+    pub fn read_int32(&mut self, bytes: &[u8]) -> Result<i32> {
+        self.read_varint64(bytes).map(|value| value as i32)
+    }
+
+    pub fn read_int64(&mut self, bytes: &[u8]) -> Result<i64> {
+        self.read_varint64(bytes).map(|value| value as i64)
+    }
+
+    pub fn read_uint32(&mut self, bytes: &[u8]) -> Result<u32> {
+        self.read_varint64(bytes).map(|value| value as u32)
+    }
+
+    pub fn read_uint64(&mut self, bytes: &[u8]) -> Result<u64> {
+        self.read_varint64(bytes)
+    }
+
+    pub fn read_sint32(&mut self, bytes: &[u8]) -> Result<i32> {
+        let value = self.read_varint64(bytes)? as u32;
+        Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+    }
+
+    pub fn read_bool(&mut self, bytes: &[u8]) -> Result<bool> {
+        self.read_varint64(bytes).map(|value| value != 0)
+    }
+
+    /// Take the next `len` bytes of the message.
+    fn take<'a>(&mut self, bytes: &'a [u8], len: u64) -> Result<&'a [u8]> {
+        if len > (self.end - self.start) as u64 {
+            return Err(Error::UnexpectedEndOfBuffer);
+        }
+        let start = self.start;
+        self.start += len as usize;
+        Ok(&bytes[start..self.start])
+    }
+
+    pub fn read_bytes<'a>(&mut self, bytes: &'a [u8]) -> Result<&'a [u8]> {
+        let len = self.read_varint64(bytes)?;
+        self.take(bytes, len)
+    }
+
+    pub fn read_string<'a>(&mut self, bytes: &'a [u8]) -> Result<&'a str> {
+        let data = self.read_bytes(bytes)?;
+        core::str::from_utf8(data).map_err(|_| Error::Utf8)
+    }
+
+    /// Read a length-delimited message, nested or at the top level.
+    pub fn read_message<'a, M: MessageRead<'a>>(&mut self, bytes: &'a [u8]) -> Result<M> {
+        let len = self.read_varint64(bytes)?;
+        let start = self.start;
+        self.take(bytes, len)?;
+
+        // the nested reader sees the whole buffer, so borrowed fields keep 'a
+        let mut nested = BytesReader {
+            start,
+            end: self.start,
+        };
+        M::from_reader(&mut nested, bytes)
+    }
+
+    /// Skip a field of a newer protocol revision.
+    pub fn read_unknown(&mut self, bytes: &[u8], tag: u32) -> Result<()> {
+        let len = match (tag & 0x7) as u8 {
+            WIRE_TYPE_VARINT => return self.read_varint64(bytes).map(|_| ()),
+            WIRE_TYPE_FIXED64 => 8,
+            WIRE_TYPE_FIXED32 => 4,
+            WIRE_TYPE_LENGTH_DELIMITED => self.read_varint64(bytes)?,
+            t => return Err(Error::UnknownWireType(t)),
+        };
+        self.take(bytes, len).map(|_| ())
+    }
+}
+
+/// Writes a message into a fixed buffer.
+pub struct Writer<'b> {
+    buf: &'b mut [u8],
+    pos: usize,
+}
+
+impl<'b> Writer<'b> {
+    pub fn new(buf: &'b mut [u8]) -> Self {
+        Writer { buf, pos: 0 }
+    }
+
+    /// Bytes written so far.
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    fn write_u8(&mut self, byte: u8) -> Result<()> {
+        let slot = self
+            .buf
+            .get_mut(self.pos)
+            .ok_or(Error::OutputBufferTooSmall)?;
+        *slot = byte;
+        self.pos += 1;
+        Ok(())
+    }
+
+    pub fn write_varint(&mut self, mut value: u64) -> Result<()> {
+        while value >= 0x80 {
+            self.write_u8(value as u8 | 0x80)?;
+            value >>= 7;
+        }
+        self.write_u8(value as u8)
+    }
+
+    pub fn write_with_tag<F>(&mut self, tag: u32, write: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()>,
+    {
+        self.write_varint(tag as u64)?;
+        write(self)
+    }
+
+    pub fn write_int32(&mut self, value: i32) -> Result<()> {
+        self.write_varint(value as u64)
+    }
+
+    pub fn write_int64(&mut self, value: i64) -> Result<()> {
+        self.write_varint(value as u64)
+    }
+
+    pub fn write_uint32(&mut self, value: u32) -> Result<()> {
+        self.write_varint(value as u64)
+    }
+
+    pub fn write_uint64(&mut self, value: u64) -> Result<()> {
+        self.write_varint(value)
+    }
+
+    pub fn write_sint32(&mut self, value: i32) -> Result<()> {
+        self.write_varint(zigzag32(value))
+    }
+
+    pub fn write_bool(&mut self, value: bool) -> Result<()> {
+        self.write_u8(value as u8)
+    }
+
+    pub fn write_bytes(&mut self, data: &[u8]) -> Result<()> {
+        self.write_varint(data.len() as u64)?;
+        let end = self.pos + data.len();
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(Error::OutputBufferTooSmall)?
+            .copy_from_slice(data);
+        self.pos = end;
+        Ok(())
+    }
+
+    pub fn write_string(&mut self, s: &str) -> Result<()> {
+        self.write_bytes(s.as_bytes())
+    }
+
+    /// Write a message with its length prefix.
+    pub fn write_message<M: MessageWrite>(&mut self, message: &M) -> Result<()> {
+        self.write_varint(message.get_size() as u64)?;
+        message.write_message(self)
+    }
+}
+
+/// Decode a message with its varint length prefix.
+pub fn deserialize_from_slice<'a, M: MessageRead<'a>>(bytes: &'a [u8]) -> Result<M> {
+    BytesReader::from_bytes(bytes).read_message(bytes)
+}
+
+/// Encode a message with its varint length prefix, returns the length written.
+pub fn serialize_into_slice<M: MessageWrite>(message: &M, out: &mut [u8]) -> Result<usize> {
+    let mut w = Writer::new(out);
+    w.write_message(message)?;
+    Ok(w.len())
+}
@@ -0,0 +1,57 @@
+/// Length of an ASIC response including the preamble.
+pub const RESPONSE_LEN: usize = 11;
+
+/// Every response from the ASICs starts with it.
+pub const PREAMBLE: [u8; 2] = [0xaa, 0x55];
+
+/// Baud rates the relay accepts from the host for USART1.
+pub const MIN_BAUD: u32 = 9_600;
+pub const MAX_BAUD: u32 = 2_000_000;
+
+/// Splits the byte stream from the ASICs into responses.
+///
+/// Bytes before a preamble are dropped, so the relay syncs again after noise
+/// on the line or after starting in the middle of a response.
+pub struct ResponseSync {
+    buf: [u8; RESPONSE_LEN],
+    len: usize,
+}
+
+impl Default for ResponseSync {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResponseSync {
+    pub const fn new() -> Self {
+        ResponseSync {
+            buf: [0; RESPONSE_LEN],
+            len: 0,
+        }
+    }
+
+    /// Feed one received byte, returns a response once it is complete.
+    pub fn push(&mut self, byte: u8) -> Option<[u8; RESPONSE_LEN]> {
+        if self.len < PREAMBLE.len() && byte != PREAMBLE[self.len] {
+            // the byte may start the next preamble
+            self.len = if byte == PREAMBLE[0] { 1 } else { 0 };
+            self.buf[0] = byte;
+            return None;
+        }
+
+        self.buf[self.len] = byte;
+        self.len += 1;
+
+        if self.len < RESPONSE_LEN {
+            return None;
+        }
+        self.len = 0;
+        Some(self.buf)
+    }
+}
+
+/// Check a baud rate the host set on the ASIC interface.
+pub fn valid_baud(baud: u32) -> Option<u32> {
+    (MIN_BAUD..=MAX_BAUD).contains(&baud).then_some(baud)
+}
@@ -0,0 +1,667 @@
+use heapless::Vec;
+
+use crate::framing::MAX_FRAME;
+use crate::protobuf::coms::{
+    mod_QOperation, mod_QRequest, mod_QResponse, mod_QResult, QBatch, QBatchReply, QEmpty, QError,
+    QHistory, QI2cScan, QI2cTransfer, QPwmConfig, QRequest, QResetInfo, QResponse, QResult,
+    QSensors, QState,
+};
+use crate::protobuf::wire::{sizeof_len, sizeof_varint, BytesReader, MessageRead, MessageWrite};
+use crate::protobuf::{deserialize_from_slice, serialize_into_slice};
+
+/// Shortest interval of the status stream, keeps room for requests on the link.
+pub const MIN_STATE_INTERVAL_MS: i32 = 100;
+/// Longest interval of the status stream, one hour.
+pub const MAX_STATE_INTERVAL_MS: i32 = 3_600_000;
+
+/// Most verbose level of the log output sent to the host, see `QLogLevel`.
+pub const MAX_LOG_LEVEL: i32 = 5;
+
+/// Longest raw I2C transfer in bytes.
+pub const MAX_TRANSFER: usize = 32;
+
+/// Largest response payload, leaves room for the `QResponse` around it.
+pub const MAX_RESPONSE_DATA: usize = MAX_FRAME - 16;
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Errors {
+    None = 0,
+    InvalidCommand = 1,
+    ErrorDeserializingRequest = 2,
+    ErrorSerializingResponse = 3,
+    ErrorDeserializingRequestData = 4,
+    ErrorSerializingResponseData = 5,
+    AccessDenied = 6,
+    I2cError = 7,
+    InvalidParameter = 8,
+    OversizedFrame = 9,
+    OutOfRange = 10,
+    Busy = 11,
+    Unsupported = 12,
+}
+
+impl Errors {
+    pub fn to_string(error: &Errors) -> &'static str {
+        match error {
+            Errors::InvalidCommand => "invalid command",
+            Errors::ErrorDeserializingRequest => "error deserializing request",
+            Errors::ErrorSerializingResponse => "error serializing response",
+            Errors::ErrorDeserializingRequestData => "error deserializing request data",
+            Errors::ErrorSerializingResponseData => "error serializing response data",
+            Errors::AccessDenied => "access denied",
+            Errors::I2cError => "i2c error",
+            Errors::InvalidParameter => "invalid parameter",
+            Errors::OversizedFrame => "frame too large",
+            Errors::OutOfRange => "value out of range",
+            Errors::Busy => "busy, try again",
+            Errors::Unsupported => "not supported",
+            _ => "unknown error",
+        }
+    }
+
+    pub fn from_i32(value: i32) -> Option<Errors> {
+        match value {
+            0 => Some(Errors::None),
+            1 => Some(Errors::InvalidCommand),
+            2 => Some(Errors::ErrorDeserializingRequest),
+            3 => Some(Errors::ErrorSerializingResponse),
+            4 => Some(Errors::ErrorDeserializingRequestData),
+            5 => Some(Errors::ErrorSerializingResponseData),
+            6 => Some(Errors::AccessDenied),
+            7 => Some(Errors::I2cError),
+            8 => Some(Errors::InvalidParameter),
+            9 => Some(Errors::OversizedFrame),
+            10 => Some(Errors::OutOfRange),
+            11 => Some(Errors::Busy),
+            12 => Some(Errors::Unsupported),
+            _ => None,
+        }
+    }
+}
+
+/// An error code with the request field it refers to.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Error {
+    pub code: Errors,
+    pub field: Option<&'static str>,
+}
+
+impl Error {
+    pub const fn field(code: Errors, field: &'static str) -> Self {
+        Error {
+            code,
+            field: Some(field),
+        }
+    }
+}
+
+impl From<Errors> for Error {
+    fn from(code: Errors) -> Self {
+        Error { code, field: None }
+    }
+}
+
+fn error_detail(error: Error) -> QError<'static> {
+    QError {
+        code: error.code as i32,
+        reason: Errors::to_string(&error.code),
+        field: error.field.unwrap_or_default(),
+    }
+}
+
+/// Build the response for a failed request, with the details for the host.
+/// `id` is the id of the request, see [`request_id`] for one that failed to
+/// decode.
+pub fn error_response(id: i32, error: Error) -> QResponse<'static> {
+    QResponse {
+        id,
+        error: error.code as i32,
+        data: &[0u8],
+        detail: Some(error_detail(error)),
+        event: None,
+        state: None,
+        log: None,
+        body: Reply::None,
+    }
+}
+
+/// Read the id of a request that failed to decode from the fields before the
+/// malformed one, 0 if it wasn't reached.
+pub fn request_id(frame: &[u8]) -> i32 {
+    let mut reader = BytesReader::from_bytes(frame);
+    // the length of the request
+    if reader.read_varint64(frame).is_err() {
+        return 0;
+    }
+    let mut id = 0;
+    while !reader.is_eof() {
+        let Ok(tag) = reader.next_tag(frame) else {
+            break;
+        };
+        let read = match tag {
+            // field 1, varint
+            0x08 => reader.read_int32(frame).map(|value| id = value),
+            _ => reader.read_unknown(frame, tag),
+        };
+        if read.is_err() {
+            break;
+        }
+    }
+    id
+}
+
+/// Wrap a status snapshot for streaming it unsolicited on the control channel.
+pub fn state_response(state: QState) -> QResponse<'static> {
+    QResponse {
+        state: Some(state),
+        ..Default::default()
+    }
+}
+
+pub enum Commands {
+    Nop = 0,
+    Control = 1,
+    Status = 2,
+    Reset = 3,
+    Shutdown = 4,
+    Sensors = 5,
+    I2cScan = 6,
+    I2cRead = 7,
+    I2cWrite = 8,
+    BringUp = 9,
+    PwmConfig = 10,
+    GetResetInfo = 11,
+    Subscribe = 12,
+    GetHistory = 13,
+    SetLogLevel = 14,
+    Batch = 15,
+    GetPwmConfig = 16,
+}
+
+impl Commands {
+    pub fn from_i32(value: i32) -> Option<Commands> {
+        match value {
+            0 => Some(Commands::Nop),
+            1 => Some(Commands::Control),
+            2 => Some(Commands::Status),
+            3 => Some(Commands::Reset),
+            4 => Some(Commands::Shutdown),
+            5 => Some(Commands::Sensors),
+            6 => Some(Commands::I2cScan),
+            7 => Some(Commands::I2cRead),
+            8 => Some(Commands::I2cWrite),
+            9 => Some(Commands::BringUp),
+            10 => Some(Commands::PwmConfig),
+            11 => Some(Commands::GetResetInfo),
+            12 => Some(Commands::Subscribe),
+            13 => Some(Commands::GetHistory),
+            14 => Some(Commands::SetLogLevel),
+            15 => Some(Commands::Batch),
+            16 => Some(Commands::GetPwmConfig),
+            _ => None,
+        }
+    }
+}
+
+/// The board side of the requests.
+///
+/// Setpoints are handed over without waiting for them to be applied, a newer
+/// setpoint replaces one that wasn't picked up yet. This keeps the control
+/// interface responsive no matter how fast the host sends them.
+///
+/// The raw I2C access is optional, boards without it answer `Unsupported`.
+#[allow(async_fn_in_trait)]
+pub trait Device {
+    /// Set the fan duties in per-mille.
+    fn set_pwm(&mut self, pwm1: u16, pwm2: u16);
+    /// Set the PWM frequency, polarity, ramp and start-up behaviour.
+    fn set_pwm_config(&mut self, config: &QPwmConfig) -> Result<(), Error>;
+    /// The PWM config currently applied.
+    async fn pwm_config(&mut self) -> Result<QPwmConfig, Error> {
+        Err(Errors::Unsupported.into())
+    }
+    fn reset(&mut self);
+    fn shutdown(&mut self);
+
+    async fn status(&mut self) -> QState;
+    async fn sensors(&mut self) -> QSensors;
+
+    async fn i2c_scan(&mut self) -> Result<Vec<u8, 112>, Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    async fn i2c_read(&mut self, _address: u8, _reg: u8, _data: &mut [u8]) -> Result<(), Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    async fn i2c_write(&mut self, _address: u8, _reg: u8, _data: &[u8]) -> Result<(), Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    /// Enable bring-up mode with the right key, disable it with 0.
+    async fn set_bringup_mode(&mut self, _key: u32) -> Result<bool, Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    /// Why the MCU was started last, see `QResetInfo`. The panic message is
+    /// borrowed from the device.
+    async fn reset_info(&mut self) -> QResetInfo<'_>;
+
+    /// Select the `EventKind`s sent to the host and the interval of the
+    /// status stream, 0 stops the events or the stream.
+    fn subscribe(&mut self, _events: u32, _state_interval_ms: u32) -> Result<(), Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    /// Send the log output from `level` up to the host, 0 stops it.
+    fn set_log_level(&mut self, _level: u8) -> Result<(), Error> {
+        Err(Errors::Unsupported.into())
+    }
+
+    /// Get a page of the telemetry history fitting into `max_size` bytes,
+    /// see `History::page`.
+    async fn history(
+        &mut self,
+        _start: u32,
+        _count: usize,
+        _max_size: usize,
+    ) -> Result<QHistory, Error> {
+        Err(Errors::Unsupported.into())
+    }
+}
+
+/// Validate the 7-bit address and register of a raw transfer.
+fn i2c_target(cmd: &QI2cTransfer) -> Result<(u8, u8), Error> {
+    if !(0x08..0x78).contains(&cmd.address) {
+        return Err(Error::field(Errors::OutOfRange, "address"));
+    }
+    if !(0..=0xff).contains(&cmd.reg) {
+        return Err(Error::field(Errors::OutOfRange, "reg"));
+    }
+    Ok((cmd.address as u8, cmd.reg as u8))
+}
+
+/// Decode the message of a revision 1 request.
+fn request_data<'a, M: MessageRead<'a>>(data: &'a [u8]) -> Result<M, Error> {
+    deserialize_from_slice(data)
+        .map_err(|_| Error::field(Errors::ErrorDeserializingRequestData, "data"))
+}
+
+/// Length of a serialized message including its varint length prefix.
+pub fn framed_size<M: MessageWrite>(message: &M) -> usize {
+    let size = message.get_size();
+    size + sizeof_varint(size as u64)
+}
+
+/// Serialize a response payload, returning its length including the varint prefix.
+fn serialize<M: MessageWrite>(message: &M, out: &mut [u8]) -> Result<usize, Error> {
+    serialize_into_slice(message, out)
+        .map_err(|_| Error::from(Errors::ErrorSerializingResponseData))
+}
+
+/// The command of a request, see `QRequest`.
+pub type Body<'a> = mod_QRequest::OneOfbody<'a>;
+/// The reply to a command, see `QResponse`.
+pub type Reply<'a> = mod_QResponse::OneOfbody<'a>;
+/// A command of a batch, see `QOperation`.
+pub type Operation<'a> = mod_QOperation::OneOfbody<'a>;
+/// The reply to an operation of a batch, see `QResult`.
+pub type OperationReply<'a> = mod_QResult::OneOfbody<'a>;
+
+// the i2c scan is the largest field of a reply borrowing the scratch buffer
+const SCRATCH: usize = 112;
+
+/// The command of a request of either revision. A revision 1 request, `op`
+/// with the message of the command in `data`, is turned into the body of a
+/// revision 2 request.
+pub fn request_body<'a>(request: &QRequest<'a>) -> Result<Body<'a>, Error> {
+    if request.body != Body::None {
+        return Ok(request.body.clone());
+    }
+
+    let op = Commands::from_i32(request.op).ok_or(Error::field(Errors::InvalidCommand, "op"))?;
+    let data = request.data;
+    Ok(match op {
+        Commands::Nop => Body::nop(QEmpty {}),
+        Commands::Control => Body::control(request_data(data)?),
+        Commands::Status => Body::status(QEmpty {}),
+        Commands::Reset => Body::reset(QEmpty {}),
+        Commands::Shutdown => Body::shutdown(QEmpty {}),
+        Commands::Sensors => Body::sensors(QEmpty {}),
+        Commands::I2cScan => Body::i2c_scan(QEmpty {}),
+        Commands::I2cRead => Body::i2c_read(request_data(data)?),
+        Commands::I2cWrite => Body::i2c_write(request_data(data)?),
+        Commands::BringUp => Body::bring_up(request_data(data)?),
+        Commands::PwmConfig => Body::pwm_config(request_data(data)?),
+        Commands::GetResetInfo => Body::reset_info(QEmpty {}),
+        Commands::Subscribe => Body::subscribe(request_data(data)?),
+        Commands::GetHistory => Body::history(request_data(data)?),
+        Commands::SetLogLevel => Body::log_level(request_data(data)?),
+        Commands::Batch => Body::batch(request_data(data)?),
+        Commands::GetPwmConfig => Body::get_pwm_config(QEmpty {}),
+    })
+}
+
+/// Run a command, the reply borrows from the device and `scratch`.
+async fn execute<'s, D: Device>(
+    device: &'s mut D,
+    body: &Body<'_>,
+    scratch: &'s mut [u8],
+) -> Result<Reply<'s>, Error> {
+    let reply = match body {
+        Body::nop(_) => Reply::None,
+        Body::control(cmd) => {
+            // older hosts send percent
+            let (max, scale) = if cmd.permille { (1000, 1) } else { (100, 10) };
+            if !(0..=max).contains(&cmd.pwm1) {
+                return Err(Error::field(Errors::OutOfRange, "pwm1"));
+            }
+            if !(0..=max).contains(&cmd.pwm2) {
+                return Err(Error::field(Errors::OutOfRange, "pwm2"));
+            }
+
+            device.set_pwm((cmd.pwm1 * scale) as u16, (cmd.pwm2 * scale) as u16);
+            Reply::None
+        }
+        Body::status(_) => Reply::status(device.status().await),
+        Body::sensors(_) => Reply::sensors(device.sensors().await),
+        Body::i2c_scan(_) => {
+            let addresses = device.i2c_scan().await?;
+
+            let data = &mut scratch[..addresses.len()];
+            data.copy_from_slice(&addresses);
+            Reply::i2c_scan(QI2cScan { addresses: data })
+        }
+        Body::i2c_read(cmd) => {
+            let (address, reg) = i2c_target(cmd)?;
+            let len = cmd.length as usize;
+            if len == 0 || len > MAX_TRANSFER {
+                return Err(Error::field(Errors::OutOfRange, "length"));
+            }
+
+            let data = &mut scratch[..len];
+            device.i2c_read(address, reg, data).await?;
+
+            Reply::i2c_read(QI2cTransfer {
+                address: cmd.address,
+                reg: cmd.reg,
+                length: cmd.length,
+                data,
+            })
+        }
+        Body::i2c_write(cmd) => {
+            let (address, reg) = i2c_target(cmd)?;
+            if cmd.data.is_empty() || cmd.data.len() > MAX_TRANSFER {
+                return Err(Error::field(Errors::OutOfRange, "data"));
+            }
+
+            device.i2c_write(address, reg, cmd.data).await?;
+            Reply::None
+        }
+        Body::bring_up(cmd) => {
+            let enabled = device.set_bringup_mode(cmd.key as u32).await?;
+            if cmd.key != 0 && !enabled {
+                return Err(Error::field(Errors::AccessDenied, "key"));
+            }
+            Reply::None
+        }
+        Body::pwm_config(cmd) => {
+            device.set_pwm_config(cmd)?;
+            Reply::None
+        }
+        Body::reset_info(_) => Reply::reset_info(device.reset_info().await),
+        Body::get_pwm_config(_) => Reply::get_pwm_config(device.pwm_config().await?),
+        Body::subscribe(cmd) => {
+            let interval = cmd.state_interval_ms;
+            if interval != 0 && !(MIN_STATE_INTERVAL_MS..=MAX_STATE_INTERVAL_MS).contains(&interval)
+            {
+                return Err(Error::field(Errors::OutOfRange, "state_interval_ms"));
+            }
+            device.subscribe(cmd.events as u32, interval as u32)?;
+            Reply::None
+        }
+        Body::history(cmd) => {
+            if cmd.start < 0 {
+                return Err(Error::field(Errors::OutOfRange, "start"));
+            }
+            if cmd.count < 0 {
+                return Err(Error::field(Errors::OutOfRange, "count"));
+            }
+            let history = device
+                .history(cmd.start as u32, cmd.count as usize, MAX_RESPONSE_DATA)
+                .await?;
+            Reply::history(history)
+        }
+        Body::log_level(cmd) => {
+            if !(0..=MAX_LOG_LEVEL).contains(&cmd.level) {
+                return Err(Error::field(Errors::OutOfRange, "level"));
+            }
+            device.set_log_level(cmd.level as u8)?;
+            Reply::None
+        }
+        Body::reset(_) => {
+            device.reset();
+            Reply::None
+        }
+        Body::shutdown(_) => {
+            device.shutdown();
+            Reply::None
+        }
+        // run by `reply_to`, batches don't nest
+        Body::batch(_) => return Err(Error::field(Errors::InvalidCommand, "batch")),
+        // an operation of a batch without a command
+        Body::None => return Err(Error::field(Errors::InvalidCommand, "body")),
+    };
+    Ok(reply)
+}
+
+/// Run a command, batches included. The reply borrows from the device and
+/// `scratch`.
+async fn reply_to<'s, D: Device>(
+    device: &'s mut D,
+    body: &Body<'_>,
+    scratch: &'s mut [u8; MAX_RESPONSE_DATA],
+) -> Result<Reply<'s>, Error> {
+    match body {
+        Body::batch(batch) => Ok(Reply::batch(run_batch(device, batch, scratch).await)),
+        body => execute(device, body, scratch).await,
+    }
+}
+
+fn operation_body<'a>(operation: &Operation<'a>) -> Body<'a> {
+    match operation.clone() {
+        Operation::nop(m) => Body::nop(m),
+        Operation::control(m) => Body::control(m),
+        Operation::status(m) => Body::status(m),
+        Operation::reset(m) => Body::reset(m),
+        Operation::shutdown(m) => Body::shutdown(m),
+        Operation::sensors(m) => Body::sensors(m),
+        Operation::i2c_scan(m) => Body::i2c_scan(m),
+        Operation::i2c_read(m) => Body::i2c_read(m),
+        Operation::i2c_write(m) => Body::i2c_write(m),
+        Operation::bring_up(m) => Body::bring_up(m),
+        Operation::pwm_config(m) => Body::pwm_config(m),
+        Operation::reset_info(m) => Body::reset_info(m),
+        Operation::subscribe(m) => Body::subscribe(m),
+        Operation::log_level(m) => Body::log_level(m),
+        Operation::get_pwm_config(m) => Body::get_pwm_config(m),
+        Operation::None => Body::None,
+    }
+}
+
+fn operation_reply(reply: Reply<'_>) -> OperationReply<'_> {
+    match reply {
+        Reply::status(m) => OperationReply::status(m),
+        Reply::sensors(m) => OperationReply::sensors(m),
+        Reply::i2c_scan(m) => OperationReply::i2c_scan(m),
+        Reply::i2c_read(m) => OperationReply::i2c_read(m),
+        Reply::reset_info(m) => OperationReply::reset_info(m),
+        Reply::get_pwm_config(m) => OperationReply::get_pwm_config(m),
+        // not the reply to any operation
+        Reply::history(_) | Reply::batch(_) | Reply::None => OperationReply::None,
+    }
+}
+
+/// Move `data` to the front of `storage`.
+fn copy<'b>(data: &[u8], storage: &mut &'b mut [u8]) -> Result<&'b [u8], Error> {
+    if data.len() > storage.len() {
+        return Err(Errors::ErrorSerializingResponseData.into());
+    }
+    let (copy, rest) = core::mem::take(storage).split_at_mut(data.len());
+    copy.copy_from_slice(data);
+    *storage = rest;
+    Ok(copy)
+}
+
+/// Copy what a reply borrows into `storage`, so the replies of a batch don't
+/// hold on to the device or the scratch buffer of their operation.
+fn detach<'b>(
+    reply: &OperationReply<'_>,
+    storage: &mut &'b mut [u8],
+) -> Result<OperationReply<'b>, Error> {
+    Ok(match reply {
+        OperationReply::status(m) => OperationReply::status(m.clone()),
+        OperationReply::sensors(m) => OperationReply::sensors(m.clone()),
+        OperationReply::get_pwm_config(m) => OperationReply::get_pwm_config(m.clone()),
+        OperationReply::i2c_scan(m) => OperationReply::i2c_scan(QI2cScan {
+            addresses: copy(m.addresses, storage)?,
+        }),
+        OperationReply::i2c_read(m) => OperationReply::i2c_read(QI2cTransfer {
+            address: m.address,
+            reg: m.reg,
+            length: m.length,
+            data: copy(m.data, storage)?,
+        }),
+        OperationReply::reset_info(m) => OperationReply::reset_info(QResetInfo {
+            cause: m.cause,
+            boots: m.boots,
+            // a copy of a str
+            panic: core::str::from_utf8(copy(m.panic.as_bytes(), storage)?).unwrap_or_default(),
+        }),
+        OperationReply::None => OperationReply::None,
+    })
+}
+
+/// Size of a result in a batch reply, with its tag and length.
+fn result_size(result: &QResult) -> usize {
+    1 + sizeof_len(result.get_size())
+}
+
+/// Whether the batch reply still fits a response with `result` added and
+/// `reserve` bytes left for the results after it.
+fn fits(reply: &QBatchReply, result: &QResult, reserve: usize) -> bool {
+    let size = reply.get_size() + result_size(result) + reserve;
+    size + sizeof_varint(size as u64) <= MAX_RESPONSE_DATA
+}
+
+/// Run the operations of a batch in order, a failing one stops an atomic
+/// batch. Every operation that ran gets a result: room for the error code of
+/// each one is kept, a reply that doesn't fit the rest of the response is
+/// replaced by `ErrorSerializingResponseData`. The results borrow `storage`.
+async fn run_batch<'b, D: Device>(
+    device: &mut D,
+    batch: &QBatch<'_>,
+    mut storage: &'b mut [u8],
+) -> QBatchReply<'b> {
+    let mut reply = QBatchReply::default();
+    // all error codes are one byte, the codes of a full batch take 32 bytes
+    let code_size = result_size(&QResult {
+        error: Errors::ErrorSerializingResponseData as i32,
+        ..Default::default()
+    });
+
+    for (i, operation) in batch.ops.iter().enumerate() {
+        let reserve = (batch.ops.len() - i - 1) * code_size;
+        let mut scratch = [0u8; SCRATCH];
+        let (body, error) =
+            match execute(device, &operation_body(&operation.body), &mut scratch).await {
+                Ok(body) => (operation_reply(body), None),
+                Err(e) => (OperationReply::None, Some(e)),
+            };
+        let result = QResult {
+            error: error.map_or(Errors::None, |e| e.code) as i32,
+            detail: error.map(error_detail),
+            body,
+        };
+        let failed = error.is_some();
+
+        let mut detached = None;
+        if fits(&reply, &result, reserve) {
+            if let Ok(body) = detach(&result.body, &mut storage) {
+                detached = Some(QResult {
+                    error: result.error,
+                    detail: error.map(error_detail),
+                    body,
+                });
+            }
+        }
+        let result = detached.unwrap_or(QResult {
+            error: match failed {
+                true => result.error,
+                false => Errors::ErrorSerializingResponseData as i32,
+            },
+            ..Default::default()
+        });
+        // as many results as operations, the reserve leaves room for it
+        let _ = reply.results.push(result);
+
+        if failed && batch.atomic {
+            break;
+        }
+    }
+    reply
+}
+
+/// Process a request of either revision. The reply to a revision 1 request
+/// is serialized into `response_data`, the reply to a revision 2 request goes
+/// into `response.body` and borrows from the device and `response_data`.
+/// Returns the length of `response.data`.
+pub async fn process_request<'b, D: Device>(
+    device: &'b mut D,
+    request: &QRequest<'_>,
+    response: &mut QResponse<'b>,
+    response_data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> Result<usize, Error> {
+    let body = request_body(request)?;
+
+    let mut response_len = 0;
+    if request.body == Body::None {
+        let mut scratch = [0u8; MAX_RESPONSE_DATA];
+        response_len = match reply_to(device, &body, &mut scratch).await? {
+            Reply::status(state) => serialize(&state, response_data)?,
+            Reply::sensors(state) => serialize(&state, response_data)?,
+            Reply::i2c_scan(state) => serialize(&state, response_data)?,
+            Reply::i2c_read(state) => serialize(&state, response_data)?,
+            Reply::reset_info(state) => serialize(&state, response_data)?,
+            Reply::history(history) => serialize(&history, response_data)?,
+            Reply::batch(reply) => serialize(&reply, response_data)?,
+            Reply::get_pwm_config(config) => serialize(&config, response_data)?,
+            Reply::None => 0,
+        };
+        response.data = &response_data[..response_len];
+    } else {
+        response.body = reply_to(device, &body, response_data).await?;
+    }
+
+    response.id = request.id;
+    response.error = Errors::None as i32;
+    Ok(response_len)
+}
+
+/// Run a command like a revision 2 request of the host, for the text
+/// protocols. The reply borrows from the device and `data`.
+pub async fn run_command<'b, D: Device>(
+    device: &'b mut D,
+    body: Body<'_>,
+    data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> Result<Reply<'b>, Error> {
+    let request = QRequest {
+        body,
+        ..Default::default()
+    };
+    let mut response = QResponse::default();
+    process_request(device, &request, &mut response, data).await?;
+    Ok(response.body)
+}
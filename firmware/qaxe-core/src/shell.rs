@@ -0,0 +1,332 @@
+//! Text commands on the control channel, for field debugging from any serial
+//! terminal. The commands are run as requests by `rpc::process_request`.
+
+use core::fmt::{self, Write};
+
+use heapless::{String, Vec};
+
+use crate::protobuf::coms::{QControl, QEmpty, QPwmConfig, QSensors, QState};
+use crate::rpc::{run_command, Body, Device, Error, Errors, Reply, MAX_RESPONSE_DATA};
+
+/// Longest command line, longer lines are rejected.
+pub const MAX_LINE: usize = 64;
+/// Largest output of a command, more is cut off.
+pub const MAX_OUTPUT: usize = 512;
+
+pub type Output = String<MAX_OUTPUT>;
+
+pub const PROMPT: &str = "qaxe> ";
+
+const HELP: &str = "\
+status                 power, temperatures, fans and faults\r\n\
+temps                  temperatures of the board and the sensors\r\n\
+fan <1|2> <percent>    set the duty of a fan\r\n\
+config get             show the PWM config\r\n\
+config set <key> <n>   change a field of the PWM config\r\n\
+reset                  reset the ASICs\r\n\
+exit                   leave the shell for protobuf requests\r\n\
+help                   this list\r\n";
+
+/// A temperature in 1/16°C, shown to a tenth.
+struct Celsius(i32);
+
+impl fmt::Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // rounded half away from zero
+        let tenths = (self.0 as i64 * 10 + 8 * self.0.signum() as i64) / 16;
+        let sign = if tenths < 0 { "-" } else { "" };
+        write!(f, "{}{}.{} C", sign, tenths.abs() / 10, tenths.abs() % 10)
+    }
+}
+
+/// A duty in per-mille, shown in percent.
+struct Percent(i32);
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}%", self.0 / 10, (self.0 % 10).abs())
+    }
+}
+
+/// Collects a typed line with echo and runs it.
+#[derive(Default)]
+pub struct Shell {
+    line: String<MAX_LINE>,
+    // the line got too long and is rejected at its end
+    overflow: bool,
+    // the LF of a CRLF doesn't end another line
+    cr: bool,
+}
+
+impl Shell {
+    pub const fn new() -> Self {
+        Shell {
+            line: String::new(),
+            overflow: false,
+            cr: false,
+        }
+    }
+
+    /// Drop the line typed so far, e.g. after the terminal was closed.
+    pub fn clear(&mut self) {
+        *self = Shell::new();
+    }
+
+    /// Whether a line was started and not ended yet.
+    pub fn pending(&self) -> bool {
+        !self.line.is_empty() || self.overflow
+    }
+
+    /// Take a received byte and echo it into `out`. Returns true at the end
+    /// of a line, run it with [`Shell::run`].
+    pub fn push(&mut self, byte: u8, out: &mut Output) -> bool {
+        let cr = core::mem::replace(&mut self.cr, byte == b'\r');
+        match byte {
+            b'\n' if cr => false,
+            b'\r' | b'\n' => {
+                let _ = out.push_str("\r\n");
+                true
+            }
+            // backspace and delete
+            0x08 | 0x7f => {
+                if !self.overflow && self.line.pop().is_some() {
+                    let _ = out.push_str("\x08 \x08");
+                }
+                false
+            }
+            b' ' | b'!'..=b'~' => {
+                if self.overflow || self.line.push(byte as char).is_err() {
+                    self.overflow = true;
+                } else {
+                    let _ = out.push(byte as char);
+                }
+                false
+            }
+            // other control characters
+            _ => false,
+        }
+    }
+
+    /// Run the line ended last, writes its output and the next prompt into
+    /// `out`. Returns false after `exit`.
+    pub async fn run<D: Device>(&mut self, device: &mut D, out: &mut Output) -> bool {
+        let line = core::mem::take(&mut self.line);
+        let result = match core::mem::take(&mut self.overflow) {
+            true => Err(Error::field(Errors::OutOfRange, "line")),
+            false => command(device, &line, out).await,
+        };
+
+        match result {
+            Ok(false) => return false,
+            Ok(true) => (),
+            Err(e) => {
+                let reason = Errors::to_string(&e.code);
+                let _ = match e.field {
+                    Some(field) => write!(out, "error: {} ({})\r\n", reason, field),
+                    None => write!(out, "error: {}, try help\r\n", reason),
+                };
+            }
+        }
+        let _ = out.push_str(PROMPT);
+        true
+    }
+}
+
+async fn status<D: Device>(device: &mut D) -> Result<QState, Error> {
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let reply = run_command(device, Body::status(QEmpty {}), &mut data).await?;
+    match reply {
+        Reply::status(state) => Ok(state),
+        _ => Err(Errors::ErrorSerializingResponseData.into()),
+    }
+}
+
+async fn sensors<D: Device>(device: &mut D) -> Result<QSensors, Error> {
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let reply = run_command(device, Body::sensors(QEmpty {}), &mut data).await?;
+    match reply {
+        Reply::sensors(sensors) => Ok(sensors),
+        _ => Err(Errors::ErrorSerializingResponseData.into()),
+    }
+}
+
+async fn pwm_config<D: Device>(device: &mut D) -> Result<QPwmConfig, Error> {
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let reply = run_command(device, Body::get_pwm_config(QEmpty {}), &mut data).await?;
+    match reply {
+        Reply::get_pwm_config(config) => Ok(config),
+        _ => Err(Errors::ErrorSerializingResponseData.into()),
+    }
+}
+
+fn number(word: &str, field: &'static str) -> Result<i32, Error> {
+    word.parse()
+        .map_err(|_| Error::field(Errors::InvalidParameter, field))
+}
+
+fn write_state(out: &mut Output, state: &QState) {
+    let power = if state.pgood_1v2 != 0 { "good" } else { "off" };
+    let _ = write!(
+        out,
+        "power    {}, vin {} mV, 1v2 {} mV, vdda {} mV\r\n",
+        power, state.vin_mv, state.v1v2_mv, state.vdda_mv
+    );
+    let _ = write!(
+        out,
+        "temps    {}, {}, mcu {}\r\n",
+        Celsius(state.temp1),
+        Celsius(state.temp2),
+        Celsius(state.mcu_temp)
+    );
+    let fans = [
+        (state.pwm1_applied, state.pwm1_target, state.fan1_rpm),
+        (state.pwm2_applied, state.pwm2_target, state.fan2_rpm),
+    ];
+    for (i, (applied, target, rpm)) in fans.into_iter().enumerate() {
+        let _ = write!(
+            out,
+            "fan{}     {} of {}, {} rpm\r\n",
+            i + 1,
+            Percent(applied),
+            Percent(target),
+            rpm
+        );
+    }
+    let _ = write!(
+        out,
+        "faults   {:#x}, failed sensors {:#x}\r\n",
+        state.faults, state.sensors_failed
+    );
+    let _ = write!(out, "uptime   {} s\r\n", state.timestamp_us / 1_000_000);
+}
+
+fn write_sensors(out: &mut Output, sensors: &QSensors) {
+    for sensor in &sensors.sensors {
+        let kind = match sensor.kind {
+            1 => "TMP",
+            2 => "INA219",
+            3 => "INA226",
+            4 => "PMBus",
+            _ => "unknown",
+        };
+        let health = match sensor.health {
+            1 => "ok",
+            2 => "degraded",
+            3 => "failed",
+            _ => "unknown",
+        };
+        let _ = write!(
+            out,
+            "{:#04x}     {}, {} {}, {} errors\r\n",
+            sensor.address,
+            Celsius(sensor.temp),
+            kind,
+            health,
+            sensor.errors
+        );
+    }
+}
+
+fn write_config(out: &mut Output, config: &QPwmConfig) {
+    let fields = [
+        ("frequency", config.frequency),
+        ("invert1", config.invert1 as i32),
+        ("invert2", config.invert2 as i32),
+        ("min_duty1", config.min_duty1),
+        ("min_duty2", config.min_duty2),
+        ("kick_duty", config.kick_duty),
+        ("kick_ms", config.kick_ms),
+        ("ramp_rate", config.ramp_rate),
+        ("shutdown_on", config.shutdown_on),
+        ("full_fans_on", config.full_fans_on),
+    ];
+    for (name, value) in fields {
+        let _ = write!(out, "{:<12} {}\r\n", name, value);
+    }
+}
+
+/// Change a field of the PWM config by its name in `QPwmConfig`.
+fn set_field(config: &mut QPwmConfig, key: &str, value: i32) -> Result<(), Error> {
+    let invert = |name| match value {
+        0 | 1 => Ok(value == 1),
+        _ => Err(Error::field(Errors::OutOfRange, name)),
+    };
+    match key {
+        "frequency" => config.frequency = value,
+        "invert1" => config.invert1 = invert("invert1")?,
+        "invert2" => config.invert2 = invert("invert2")?,
+        "min_duty1" => config.min_duty1 = value,
+        "min_duty2" => config.min_duty2 = value,
+        "kick_duty" => config.kick_duty = value,
+        "kick_ms" => config.kick_ms = value,
+        "ramp_rate" => config.ramp_rate = value,
+        "shutdown_on" => config.shutdown_on = value,
+        "full_fans_on" => config.full_fans_on = value,
+        _ => return Err(Error::field(Errors::InvalidParameter, "key")),
+    }
+    Ok(())
+}
+
+/// Run a command line, returns false for `exit`.
+async fn command<D: Device>(device: &mut D, line: &str, out: &mut Output) -> Result<bool, Error> {
+    let mut words = Vec::<&str, 4>::new();
+    for word in line.split_ascii_whitespace() {
+        words
+            .push(word)
+            .map_err(|_| Error::from(Errors::InvalidCommand))?;
+    }
+
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    match words[..] {
+        [] => (),
+        ["help"] => {
+            let _ = out.push_str(HELP);
+        }
+        ["status"] => write_state(out, &status(device).await?),
+        ["temps"] => {
+            let state = status(device).await?;
+            let _ = write!(
+                out,
+                "board    {}, {}, mcu {}\r\n",
+                Celsius(state.temp1),
+                Celsius(state.temp2),
+                Celsius(state.mcu_temp)
+            );
+            write_sensors(out, &sensors(device).await?);
+        }
+        ["fan", fan, percent] => {
+            let fan = number(fan, "fan")?;
+            if !(1..=2).contains(&fan) {
+                return Err(Error::field(Errors::OutOfRange, "fan"));
+            }
+            let duty = number(percent, "percent")?.saturating_mul(10);
+
+            // the other fan keeps its setpoint
+            let state = status(device).await?;
+            let mut duties = [state.pwm1_target, state.pwm2_target];
+            duties[fan as usize - 1] = duty;
+            let control = QControl {
+                pwm1: duties[0],
+                pwm2: duties[1],
+                permille: true,
+                ..Default::default()
+            };
+            run_command(device, Body::control(control), &mut data).await?;
+            let _ = write!(out, "fan{} set to {}\r\n", fan, Percent(duty));
+        }
+        ["reset"] => {
+            run_command(device, Body::reset(QEmpty {}), &mut data).await?;
+            let _ = out.push_str("resetting the ASICs\r\n");
+        }
+        ["config", "get"] => write_config(out, &pwm_config(device).await?),
+        ["config", "set", key, value] => {
+            let mut config = pwm_config(device).await?;
+            set_field(&mut config, key, number(value, "value")?)?;
+            run_command(device, Body::pwm_config(config.clone()), &mut data).await?;
+            write_config(out, &config);
+        }
+        ["exit"] => return Ok(false),
+        _ => return Err(Errors::InvalidCommand.into()),
+    }
+    Ok(true)
+}
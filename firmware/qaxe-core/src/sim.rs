@@ -0,0 +1,334 @@
+//! Byte-level simulation of a BM1366/BM1368/BM1370 chain for host tests.
+//!
+//! Models what the relay and the mining software see on the UART: chip
+//! enumeration, register reads and writes, jobs answered with nonces and
+//! the switch to the fast baud rate. No hashing is done, the nonces are
+//! derived from the job but don't meet any target.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::relay::{PREAMBLE, RESPONSE_LEN};
+
+/// Every command to the ASICs starts with it.
+pub const COMMAND_PREAMBLE: [u8; 2] = [0x55, 0xaa];
+
+pub const TYPE_JOB: u8 = 0x20;
+pub const TYPE_CMD: u8 = 0x40;
+pub const GROUP_ALL: u8 = 0x10;
+pub const CMD_SET_ADDRESS: u8 = 0x00;
+pub const CMD_WRITE: u8 = 0x01;
+pub const CMD_READ: u8 = 0x02;
+pub const CMD_INACTIVE: u8 = 0x03;
+
+pub const REG_CHIP_ID: u8 = 0x00;
+pub const REG_FAST_UART: u8 = 0x28;
+
+/// `REG_FAST_UART` value the mining software writes for 1 Mbaud, the only
+/// fast configuration that is modelled.
+pub const FAST_UART_1M: u32 = 0x1130_0200;
+
+pub const DEFAULT_BAUD: u32 = 115_200;
+pub const FAST_BAUD: u32 = 1_000_000;
+
+// marks nonce responses in the last byte, register responses have it clear
+const RESPONSE_JOB: u8 = 0x80;
+
+// longest packet, a job: type, length, 82 bytes of job and the crc16
+const MAX_PACKET: usize = 86;
+
+/// CRC5 of the first `bits` bits of `data`, MSB first, as used in commands
+/// and responses.
+pub fn crc5_bits(data: &[u8], bits: usize) -> u8 {
+    let mut crc = 0x1f;
+    for i in 0..bits {
+        let bit = (data[i / 8] >> (7 - i % 8)) & 1;
+        let feedback = ((crc >> 4) & 1) ^ bit;
+        crc = (crc << 1) & 0x1f;
+        if feedback != 0 {
+            crc ^= 0x05;
+        }
+    }
+    crc
+}
+
+pub fn crc5(data: &[u8]) -> u8 {
+    crc5_bits(data, data.len() * 8)
+}
+
+/// CRC16/CCITT-FALSE as used in job packets.
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xffffu16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Build a command with its preamble and CRC5.
+pub fn command(header: u8, data: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::from(COMMAND_PREAMBLE);
+    packet.push(header);
+    packet.push(data.len() as u8 + 3);
+    packet.extend_from_slice(data);
+    packet.push(crc5(&packet[2..]));
+    packet
+}
+
+/// Build a job packet with its preamble and CRC16.
+pub fn job(data: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::from(COMMAND_PREAMBLE);
+    packet.push(TYPE_JOB | CMD_WRITE);
+    packet.push(data.len() as u8 + 4);
+    packet.extend_from_slice(data);
+    let crc = crc16(&packet[2..]);
+    packet.extend_from_slice(&crc.to_be_bytes());
+    packet
+}
+
+/// Build a response as the chips send it.
+pub fn response(
+    value: u32,
+    chip: u8,
+    reg_or_job: u8,
+    version: u16,
+    job: bool,
+) -> [u8; RESPONSE_LEN] {
+    let mut response = [0u8; RESPONSE_LEN];
+    response[..2].copy_from_slice(&PREAMBLE);
+    response[2..6].copy_from_slice(&value.to_be_bytes());
+    response[6] = chip;
+    response[7] = reg_or_job;
+    response[8..10].copy_from_slice(&version.to_be_bytes());
+
+    let flags = if job { RESPONSE_JOB } else { 0 };
+    response[10] = flags;
+    // the crc covers the flag bits in front of it
+    response[10] |= crc5_bits(&response[2..], 8 * 8 + 3);
+    response
+}
+
+/// Check the CRC5 of a response.
+pub fn response_valid(response: &[u8; RESPONSE_LEN]) -> bool {
+    response[..2] == PREAMBLE && crc5(&response[2..]) == 0
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Model {
+    Bm1366,
+    Bm1368,
+    Bm1370,
+}
+
+impl Model {
+    pub fn chip_id(self) -> u16 {
+        match self {
+            Model::Bm1366 => 0x1366,
+            Model::Bm1368 => 0x1368,
+            Model::Bm1370 => 0x1370,
+        }
+    }
+}
+
+struct Chip {
+    address: Option<u8>,
+    registers: [u32; 256],
+}
+
+/// A chain of chips on one UART.
+pub struct Chain {
+    model: Model,
+    chips: Vec<Chip>,
+    baud: u32,
+    input: Vec<u8>,
+    output: VecDeque<u8>,
+    nonces_per_job: u32,
+    /// packets dropped because of a bad length or CRC
+    pub errors: u32,
+}
+
+impl Chain {
+    pub fn new(model: Model, chips: usize) -> Self {
+        let chips = (0..chips)
+            .map(|_| {
+                let mut registers = [0; 256];
+                registers[REG_CHIP_ID as usize] = (model.chip_id() as u32) << 16;
+                Chip {
+                    address: None,
+                    registers,
+                }
+            })
+            .collect();
+
+        Chain {
+            model,
+            chips,
+            baud: DEFAULT_BAUD,
+            input: Vec::new(),
+            output: VecDeque::new(),
+            nonces_per_job: 1,
+            errors: 0,
+        }
+    }
+
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    /// Baud rate the chips currently talk at.
+    pub fn baud(&self) -> u32 {
+        self.baud
+    }
+
+    /// Chips come out of reset without address at the default baud rate.
+    pub fn reset(&mut self) {
+        *self = Chain {
+            nonces_per_job: self.nonces_per_job,
+            ..Chain::new(self.model, self.chips.len())
+        };
+    }
+
+    pub fn set_nonces_per_job(&mut self, nonces: u32) {
+        self.nonces_per_job = nonces;
+    }
+
+    /// Addresses assigned during enumeration, in chain order.
+    pub fn addresses(&self) -> Vec<Option<u8>> {
+        self.chips.iter().map(|chip| chip.address).collect()
+    }
+
+    pub fn register(&self, chip: usize, reg: u8) -> u32 {
+        self.chips[chip].registers[reg as usize]
+    }
+
+    /// Bytes sent to the chips at `baud`, at the wrong rate they are noise.
+    pub fn receive(&mut self, baud: u32, bytes: &[u8]) {
+        if baud != self.baud {
+            return;
+        }
+        self.input.extend_from_slice(bytes);
+        while self.parse_packet() {}
+    }
+
+    /// Bytes sent by the chips, read at `baud`. At the wrong rate they come
+    /// out garbled.
+    pub fn transmit(&mut self, baud: u32) -> Vec<u8> {
+        let garble = if baud == self.baud { 0 } else { 0x5a };
+        self.output.drain(..).map(|byte| byte ^ garble).collect()
+    }
+
+    /// Parse the next packet from the input, returns false if it isn't complete yet.
+    fn parse_packet(&mut self) -> bool {
+        // skip to the next preamble
+        let start = self
+            .input
+            .windows(2)
+            .position(|w| w == COMMAND_PREAMBLE)
+            .unwrap_or(self.input.len().saturating_sub(1));
+        self.input.drain(..start);
+
+        if self.input.len() < 4 {
+            return false;
+        }
+        let len = self.input[3] as usize;
+        if !(3..=MAX_PACKET).contains(&len) {
+            self.errors += 1;
+            self.input.drain(..2);
+            return true;
+        }
+        if self.input.len() < 2 + len {
+            return false;
+        }
+
+        let packet: Vec<u8> = self.input.drain(..2 + len).skip(2).collect();
+        let header = packet[0];
+        if header & TYPE_JOB != 0 {
+            if len < 5 || crc16(&packet) != 0 {
+                self.errors += 1;
+                return true;
+            }
+            self.job(&packet[2..len - 2]);
+        } else {
+            if crc5(&packet[..len - 1]) != packet[len - 1] {
+                self.errors += 1;
+                return true;
+            }
+            self.command(header, &packet[2..len - 1]);
+        }
+        true
+    }
+
+    fn command(&mut self, header: u8, data: &[u8]) {
+        let all = header & GROUP_ALL != 0;
+        let (address, data) = match data {
+            [address, rest @ ..] => (*address, rest),
+            [] => return,
+        };
+        let selected = |chip: &Chip| all || chip.address == Some(address);
+
+        match header & 0x0f {
+            CMD_INACTIVE => {
+                for chip in &mut self.chips {
+                    chip.address = None;
+                }
+            }
+            CMD_SET_ADDRESS => {
+                // the first chip without an address takes it
+                if let Some(chip) = self.chips.iter_mut().find(|c| c.address.is_none()) {
+                    chip.address = Some(address);
+                }
+            }
+            CMD_WRITE => {
+                let [reg, a, b, c, d] = data else {
+                    return;
+                };
+                let value = u32::from_be_bytes([*a, *b, *c, *d]);
+                for chip in self.chips.iter_mut().filter(|c| selected(c)) {
+                    chip.registers[*reg as usize] = value;
+                }
+                if *reg == REG_FAST_UART {
+                    self.baud = if value == FAST_UART_1M {
+                        FAST_BAUD
+                    } else {
+                        DEFAULT_BAUD
+                    };
+                }
+            }
+            CMD_READ => {
+                let [reg] = data else {
+                    return;
+                };
+                for chip in self.chips.iter().filter(|c| selected(c)) {
+                    let mut value = chip.registers[*reg as usize];
+                    if *reg == REG_CHIP_ID {
+                        value |= chip.address.unwrap_or(0) as u32;
+                    }
+                    let response = response(value, chip.address.unwrap_or(0), *reg, 0, false);
+                    self.output.extend(response);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn job(&mut self, data: &[u8]) {
+        let Some(&job_id) = data.first() else {
+            return;
+        };
+        let seed = crc16(data) as u32;
+        for i in 0..self.nonces_per_job {
+            let chip = (i as usize) % self.chips.len().max(1);
+            let address = self.chips.get(chip).and_then(|c| c.address).unwrap_or(0);
+            let nonce = seed.wrapping_mul(0x9e37_79b9).wrapping_add(i);
+            let response = response(nonce, address, job_id, i as u16, true);
+            self.output.extend(response);
+        }
+    }
+}
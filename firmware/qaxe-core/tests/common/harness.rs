@@ -0,0 +1,243 @@
+//! Request handling as the firmware does it, checked not to use the heap.
+//!
+//! Shared by the property tests and the fuzz targets.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use heapless::Vec as HVec;
+use qaxe_core::framing::MAX_FRAME;
+use qaxe_core::history::{History, Sample};
+use qaxe_core::protobuf::coms::{
+    QHistory, QPwmConfig, QRequest, QResetInfo, QResponse, QSensor, QSensors, QState,
+};
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core::rpc::{self, error_response, Device, Error, Errors, MAX_RESPONSE_DATA};
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(delta: isize) {
+    // the thread locals are gone while the thread shuts down
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + delta);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+/// Counts the heap in use per thread, so tests running in parallel don't
+/// disturb each other.
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Run `f` and return the most heap it had in use at once.
+pub fn peak_heap<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    LIVE.with(|live| live.set(0));
+    PEAK.with(|peak| peak.set(0));
+    let result = f();
+    (result, PEAK.with(|peak| peak.get()) as usize)
+}
+
+/// Poll a future once, requests to `FullDevice` never wait.
+pub fn poll_once<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    match pin!(future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("request waited"),
+    }
+}
+
+/// A device supporting every request, answering with the largest data the
+/// firmware has.
+pub struct FullDevice {
+    pub pwm: (u16, u16),
+    pub bringup: bool,
+    pub config: QPwmConfig,
+    pub history: History<48>,
+}
+
+impl Default for FullDevice {
+    fn default() -> Self {
+        let mut history = History::new();
+        for i in 0..48 {
+            // negative temperatures take the longest varints
+            let temp = if i % 2 == 0 { -1 } else { 40 * 16 };
+            history.add_sample(Sample {
+                temps: [temp, temp],
+                duties: [1000, 1000],
+            });
+            history.add_faults(u32::MAX);
+            history.close(i * 1_800_000);
+        }
+
+        FullDevice {
+            pwm: (0, 0),
+            bringup: false,
+            config: QPwmConfig {
+                frequency: 25_000,
+                ..Default::default()
+            },
+            history,
+        }
+    }
+}
+
+impl Device for FullDevice {
+    fn set_pwm(&mut self, pwm1: u16, pwm2: u16) {
+        self.pwm = (pwm1, pwm2);
+    }
+
+    fn set_pwm_config(&mut self, config: &QPwmConfig) -> Result<(), Error> {
+        self.config = config.clone();
+        Ok(())
+    }
+
+    async fn pwm_config(&mut self) -> Result<QPwmConfig, Error> {
+        Ok(self.config.clone())
+    }
+
+    fn reset(&mut self) {}
+
+    fn shutdown(&mut self) {}
+
+    async fn status(&mut self) -> QState {
+        QState {
+            pgood_1v2: 1,
+            temp1: -40 * 16,
+            temp2: -40 * 16,
+            vin_mv: 12_000,
+            v1v2_mv: 1_200,
+            vdda_mv: 3_300,
+            mcu_temp: -40 * 16,
+            faults: -1,
+            sensors_failed: 0xf,
+            pwm1_applied: 1000,
+            pwm2_applied: 1000,
+            pwm1_target: 1000,
+            pwm2_target: 1000,
+            fan1_rpm: 20_000,
+            fan2_rpm: 20_000,
+            timestamp_us: u64::MAX,
+        }
+    }
+
+    async fn sensors(&mut self) -> QSensors {
+        // MAX_SENSORS of the firmware
+        let sensor = QSensor {
+            address: 0x4f,
+            kind: 3,
+            temp: 125 * 16,
+            voltage_mv: 26_000,
+            current_ma: 20_000,
+            power_mw: 500_000,
+            health: 3,
+            errors: 1_000_000,
+        };
+        QSensors {
+            sensors: core::iter::repeat_n(sensor, 4).collect(),
+        }
+    }
+
+    async fn i2c_scan(&mut self) -> Result<HVec<u8, 112>, Error> {
+        Ok((0x08..0x78).collect())
+    }
+
+    async fn i2c_read(&mut self, _address: u8, _reg: u8, data: &mut [u8]) -> Result<(), Error> {
+        data.fill(0xff);
+        Ok(())
+    }
+
+    async fn i2c_write(&mut self, _address: u8, _reg: u8, _data: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn set_bringup_mode(&mut self, key: u32) -> Result<bool, Error> {
+        self.bringup = key == 0x5141_5845;
+        Ok(self.bringup)
+    }
+
+    async fn reset_info(&mut self) -> QResetInfo<'_> {
+        QResetInfo {
+            cause: 0x1ff,
+            boots: i32::MAX,
+            panic:
+                "panicked at src/bin/qaxe.rs:1234:5: called `Option::unwrap()` on a `None` value",
+        }
+    }
+
+    fn subscribe(&mut self, _events: u32, _state_interval_ms: u32) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn set_log_level(&mut self, _level: u8) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn history(
+        &mut self,
+        start: u32,
+        count: usize,
+        max_size: usize,
+    ) -> Result<QHistory, Error> {
+        Ok(self.history.page(start, count, max_size))
+    }
+}
+
+/// Decode and process one frame like the firmware's `handle_frame`.
+pub fn process_frame<'b>(
+    device: &'b mut FullDevice,
+    frame: &[u8],
+    data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> QResponse<'b> {
+    let request: QRequest = match deserialize_from_slice(frame) {
+        Ok(request) => request,
+        Err(_) => {
+            return error_response(
+                rpc::request_id(frame),
+                Errors::ErrorDeserializingRequest.into(),
+            )
+        }
+    };
+
+    let mut response = QResponse::default();
+    if let Err(e) = poll_once(rpc::process_request(device, &request, &mut response, data)) {
+        response = error_response(request.id, e);
+    }
+    response
+}
+
+/// Handle a frame and check it doesn't touch the heap, the firmware has
+/// none, and the response fits its frame buffer. Returns the serialized
+/// response.
+pub fn check_frame(device: &mut FullDevice, frame: &[u8]) -> std::vec::Vec<u8> {
+    let mut bytes = [0u8; MAX_FRAME];
+    let (len, heap) = peak_heap(|| {
+        let mut data = [0u8; MAX_RESPONSE_DATA];
+        let response = process_frame(device, frame, &mut data);
+        serialize_into_slice(&response, &mut bytes).unwrap_or_else(|e| {
+            let len = rpc::framed_size(&response);
+            panic!("{} byte response doesn't fit: {:?}", len, e)
+        })
+    });
+    assert_eq!(heap, 0, "heap used");
+    bytes[..len].to_vec()
+}
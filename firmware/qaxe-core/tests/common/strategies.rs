@@ -0,0 +1,324 @@
+//! Strategies for every message in `coms.proto`.
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use qaxe_core::protobuf::coms::{
+    QBatch, QBatchReply, QBringUp, QControl, QEmpty, QError, QEvent, QHistory, QHistoryInterval,
+    QHistoryRequest, QI2cScan, QI2cTransfer, QLog, QLogLevel, QOperation, QPwmConfig, QRequest,
+    QResetInfo, QResponse, QResult, QSensor, QSensors, QState, QSubscribe,
+};
+use qaxe_core::protobuf::serialize_into_slice;
+use qaxe_core::protobuf::wire::MessageWrite;
+use qaxe_core::rpc::{framed_size, Body, Operation, OperationReply, Reply};
+
+/// Serialize with the varint length prefix, as sent on the link. The buffer
+/// has exactly `framed_size` bytes, so a wrong `get_size` fails.
+pub fn encode<M: MessageWrite>(message: &M) -> Vec<u8> {
+    let mut bytes = vec![0u8; framed_size(message)];
+    let len = serialize_into_slice(message, &mut bytes).unwrap();
+    assert_eq!(len, bytes.len());
+    bytes
+}
+
+// messages borrow their data, the test cases leak it
+fn bytes(max: usize) -> impl Strategy<Value = &'static [u8]> {
+    vec(any::<u8>(), 0..=max).prop_map(|data| &*data.leak())
+}
+
+fn text() -> impl Strategy<Value = &'static str> {
+    "\\PC{0,40}".prop_map(|s| &*s.leak())
+}
+
+pub fn request() -> impl Strategy<Value = QRequest<'static>> {
+    (any::<i32>(), any::<i32>(), bytes(64), body()).prop_map(|(id, op, data, body)| QRequest {
+        id,
+        op,
+        data,
+        body,
+    })
+}
+
+/// Commands of revision 2 requests, `Body::None` for revision 1.
+pub fn body() -> impl Strategy<Value = Body<'static>> {
+    prop_oneof![
+        Just(Body::None),
+        Just(Body::nop(QEmpty {})),
+        control().prop_map(Body::control),
+        Just(Body::status(QEmpty {})),
+        Just(Body::reset(QEmpty {})),
+        Just(Body::shutdown(QEmpty {})),
+        Just(Body::sensors(QEmpty {})),
+        Just(Body::i2c_scan(QEmpty {})),
+        i2c_transfer().prop_map(Body::i2c_read),
+        i2c_transfer().prop_map(Body::i2c_write),
+        bring_up().prop_map(Body::bring_up),
+        pwm_config().prop_map(Body::pwm_config),
+        Just(Body::reset_info(QEmpty {})),
+        subscribe().prop_map(Body::subscribe),
+        history_request().prop_map(Body::history),
+        log_level().prop_map(Body::log_level),
+        batch().prop_map(Body::batch),
+        Just(Body::get_pwm_config(QEmpty {})),
+    ]
+}
+
+/// Replies to revision 2 requests.
+pub fn reply() -> impl Strategy<Value = Reply<'static>> {
+    prop_oneof![
+        Just(Reply::None),
+        state().prop_map(Reply::status),
+        sensors().prop_map(Reply::sensors),
+        i2c_scan().prop_map(Reply::i2c_scan),
+        i2c_transfer().prop_map(Reply::i2c_read),
+        reset_info().prop_map(Reply::reset_info),
+        history().prop_map(Reply::history),
+        batch_reply().prop_map(Reply::batch),
+        pwm_config().prop_map(Reply::get_pwm_config),
+    ]
+}
+
+/// The commands of a batch, `Operation::None` for an empty one.
+pub fn operation() -> impl Strategy<Value = QOperation<'static>> {
+    prop_oneof![
+        Just(Operation::None),
+        Just(Operation::nop(QEmpty {})),
+        control().prop_map(Operation::control),
+        Just(Operation::status(QEmpty {})),
+        Just(Operation::reset(QEmpty {})),
+        Just(Operation::shutdown(QEmpty {})),
+        Just(Operation::sensors(QEmpty {})),
+        Just(Operation::i2c_scan(QEmpty {})),
+        i2c_transfer().prop_map(Operation::i2c_read),
+        i2c_transfer().prop_map(Operation::i2c_write),
+        bring_up().prop_map(Operation::bring_up),
+        pwm_config().prop_map(Operation::pwm_config),
+        Just(Operation::reset_info(QEmpty {})),
+        subscribe().prop_map(Operation::subscribe),
+        log_level().prop_map(Operation::log_level),
+        Just(Operation::get_pwm_config(QEmpty {})),
+    ]
+    .prop_map(|body| QOperation { body })
+}
+
+pub fn batch() -> impl Strategy<Value = QBatch<'static>> {
+    (vec(operation(), 0..=8), any::<bool>()).prop_map(|(ops, atomic)| QBatch {
+        ops: heapless::Vec::from_slice(&ops).unwrap(),
+        atomic,
+    })
+}
+
+pub fn result() -> impl Strategy<Value = QResult<'static>> {
+    let body = prop_oneof![
+        Just(OperationReply::None),
+        state().prop_map(OperationReply::status),
+        sensors().prop_map(OperationReply::sensors),
+        i2c_scan().prop_map(OperationReply::i2c_scan),
+        i2c_transfer().prop_map(OperationReply::i2c_read),
+        reset_info().prop_map(OperationReply::reset_info),
+        pwm_config().prop_map(OperationReply::get_pwm_config),
+    ];
+    (any::<i32>(), proptest::option::of(error()), body).prop_map(|(error, detail, body)| QResult {
+        error,
+        detail,
+        body,
+    })
+}
+
+pub fn batch_reply() -> impl Strategy<Value = QBatchReply<'static>> {
+    vec(result(), 0..=8).prop_map(|results| QBatchReply {
+        results: heapless::Vec::from_slice(&results).unwrap(),
+    })
+}
+
+pub fn error() -> impl Strategy<Value = QError<'static>> {
+    (any::<i32>(), text(), text()).prop_map(|(code, reason, field)| QError {
+        code,
+        reason,
+        field,
+    })
+}
+
+pub fn control() -> impl Strategy<Value = QControl> {
+    (any::<i32>(), any::<i32>(), any::<i32>(), any::<bool>()).prop_map(
+        |(state_1v2, pwm1, pwm2, permille)| QControl {
+            state_1v2,
+            pwm1,
+            pwm2,
+            permille,
+        },
+    )
+}
+
+pub fn state() -> impl Strategy<Value = QState> {
+    (any::<[i32; 15]>(), any::<u64>()).prop_map(|(v, timestamp_us)| QState {
+        pgood_1v2: v[0],
+        temp1: v[1],
+        temp2: v[2],
+        vin_mv: v[3],
+        v1v2_mv: v[4],
+        vdda_mv: v[5],
+        mcu_temp: v[6],
+        faults: v[7],
+        sensors_failed: v[8],
+        pwm1_applied: v[9],
+        pwm2_applied: v[10],
+        pwm1_target: v[11],
+        pwm2_target: v[12],
+        fan1_rpm: v[13],
+        fan2_rpm: v[14],
+        timestamp_us,
+    })
+}
+
+pub fn sensor() -> impl Strategy<Value = QSensor> {
+    any::<[i32; 8]>().prop_map(|v| QSensor {
+        address: v[0],
+        kind: v[1],
+        temp: v[2],
+        voltage_mv: v[3],
+        current_ma: v[4],
+        power_mw: v[5],
+        health: v[6],
+        errors: v[7],
+    })
+}
+
+pub fn sensors() -> impl Strategy<Value = QSensors> {
+    vec(sensor(), 0..=4).prop_map(|sensors| QSensors {
+        sensors: heapless::Vec::from_slice(&sensors).unwrap(),
+    })
+}
+
+pub fn i2c_scan() -> impl Strategy<Value = QI2cScan<'static>> {
+    bytes(112).prop_map(|addresses| QI2cScan { addresses })
+}
+
+pub fn i2c_transfer() -> impl Strategy<Value = QI2cTransfer<'static>> {
+    (any::<[i32; 3]>(), bytes(40)).prop_map(|(v, data)| QI2cTransfer {
+        address: v[0],
+        reg: v[1],
+        length: v[2],
+        data,
+    })
+}
+
+pub fn bring_up() -> impl Strategy<Value = QBringUp> {
+    any::<i32>().prop_map(|key| QBringUp { key })
+}
+
+pub fn pwm_config() -> impl Strategy<Value = QPwmConfig> {
+    (any::<[i32; 8]>(), any::<[bool; 2]>()).prop_map(|(v, invert)| QPwmConfig {
+        frequency: v[0],
+        invert1: invert[0],
+        invert2: invert[1],
+        min_duty1: v[1],
+        min_duty2: v[2],
+        kick_duty: v[3],
+        kick_ms: v[4],
+        ramp_rate: v[5],
+        shutdown_on: v[6],
+        full_fans_on: v[7],
+    })
+}
+
+pub fn reset_info() -> impl Strategy<Value = QResetInfo<'static>> {
+    (any::<i32>(), any::<i32>(), text()).prop_map(|(cause, boots, panic)| QResetInfo {
+        cause,
+        boots,
+        panic,
+    })
+}
+
+pub fn event() -> impl Strategy<Value = QEvent> {
+    any::<[i32; 4]>().prop_map(|v| QEvent {
+        seq: v[0],
+        timestamp: v[1],
+        kind: v[2],
+        value: v[3],
+    })
+}
+
+pub fn subscribe() -> impl Strategy<Value = QSubscribe> {
+    any::<[i32; 2]>().prop_map(|v| QSubscribe {
+        events: v[0],
+        state_interval_ms: v[1],
+    })
+}
+
+pub fn history_request() -> impl Strategy<Value = QHistoryRequest> {
+    any::<[i32; 2]>().prop_map(|v| QHistoryRequest {
+        start: v[0],
+        count: v[1],
+    })
+}
+
+pub fn history_interval() -> impl Strategy<Value = QHistoryInterval> {
+    any::<[i32; 13]>().prop_map(|v| QHistoryInterval {
+        seq: v[0],
+        end: v[1],
+        samples: v[2],
+        temp1_min: v[3],
+        temp2_min: v[4],
+        temp1_max: v[5],
+        temp2_max: v[6],
+        temp1_avg: v[7],
+        temp2_avg: v[8],
+        pwm1_avg: v[9],
+        pwm2_avg: v[10],
+        pgood_losses: v[11],
+        faults: v[12],
+    })
+}
+
+pub fn history() -> impl Strategy<Value = QHistory> {
+    (vec(history_interval(), 0..=8), any::<i32>()).prop_map(|(intervals, next)| QHistory {
+        intervals: heapless::Vec::from_slice(&intervals).unwrap(),
+        next,
+    })
+}
+
+pub fn log_level() -> impl Strategy<Value = QLogLevel> {
+    any::<i32>().prop_map(|level| QLogLevel { level })
+}
+
+pub fn log() -> impl Strategy<Value = QLog<'static>> {
+    (bytes(200), any::<i32>()).prop_map(|(data, dropped)| QLog { data, dropped })
+}
+
+pub fn response() -> impl Strategy<Value = QResponse<'static>> {
+    (
+        any::<[i32; 2]>(),
+        bytes(64),
+        proptest::option::of(error()),
+        proptest::option::of(event()),
+        proptest::option::of(state()),
+        proptest::option::of(log()),
+        reply(),
+    )
+        .prop_map(|(v, data, detail, event, state, log, body)| QResponse {
+            id: v[0],
+            error: v[1],
+            data,
+            detail,
+            event,
+            state,
+            log,
+            body,
+        })
+}
+
+/// Payloads of the requests that carry one, valid messages of any type
+/// and random bytes.
+pub fn payload() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        vec(any::<u8>(), 0..64),
+        control().prop_map(|m| encode(&m)),
+        i2c_transfer().prop_map(|m| encode(&m)),
+        bring_up().prop_map(|m| encode(&m)),
+        pwm_config().prop_map(|m| encode(&m)),
+        subscribe().prop_map(|m| encode(&m)),
+        history_request().prop_map(|m| encode(&m)),
+        log_level().prop_map(|m| encode(&m)),
+        batch().prop_map(|m| encode(&m)),
+    ]
+}
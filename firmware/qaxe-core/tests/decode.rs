@@ -0,0 +1,281 @@
+mod common;
+
+use common::harness::{check_frame, peak_heap, poll_once, FullDevice};
+use common::strategies::{body, control, encode, payload, request};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use qaxe_core::framing::{Detector, Protocol, MAX_FRAME};
+use qaxe_core::protobuf::coms::{
+    QBatch, QBatchReply, QControl, QEmpty, QHistoryRequest, QOperation, QRequest, QResponse,
+};
+use qaxe_core::protobuf::deserialize_from_slice;
+use qaxe_core::rpc::{
+    error_response, framed_size, process_request, Body, Commands, Error, Errors, Operation,
+    OperationReply, Reply, MAX_RESPONSE_DATA,
+};
+
+fn decode(bytes: &[u8]) -> QResponse<'_> {
+    deserialize_from_slice(bytes).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn any_frame_is_answered(frame in vec(any::<u8>(), 0..MAX_FRAME)) {
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &frame);
+        decode(&bytes);
+    }
+
+    #[test]
+    fn any_request_is_answered(
+        id in any::<i32>(),
+        // one past both ends of the commands
+        op in -1..=Commands::GetPwmConfig as i32 + 1,
+        data in payload(),
+    ) {
+        let request = QRequest {
+            id,
+            op,
+            data: &data,
+            ..Default::default()
+        };
+        let frame = encode(&request);
+        prop_assume!(frame.len() <= MAX_FRAME);
+
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &frame);
+        let response = decode(&bytes);
+        prop_assert_eq!(response.id, id);
+        if response.error == Errors::None as i32 {
+            prop_assert_eq!(response.body, Reply::None);
+        } else {
+            let detail = response.detail.unwrap();
+            prop_assert_eq!(detail.code, response.error);
+            prop_assert!(Errors::from_i32(detail.code).is_some());
+        }
+    }
+
+    #[test]
+    fn any_body_is_answered(id in any::<i32>(), op in any::<i32>(), body in body()) {
+        prop_assume!(body != Body::None);
+        let request = QRequest {
+            id,
+            op,
+            body,
+            ..Default::default()
+        };
+        let frame = encode(&request);
+        prop_assume!(frame.len() <= MAX_FRAME);
+
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &frame);
+        let response = decode(&bytes);
+        if response.error == Errors::None as i32 {
+            prop_assert_eq!(response.id, id);
+            prop_assert!(response.data.is_empty());
+        } else {
+            let detail = response.detail.unwrap();
+            prop_assert_eq!(detail.code, response.error);
+            prop_assert!(Errors::from_i32(detail.code).is_some());
+        }
+    }
+
+    #[test]
+    fn requests_are_detected_as_protobuf(request in request()) {
+        let frame = encode(&request);
+        prop_assume!(frame.len() <= MAX_FRAME);
+        let mut detector = Detector::new();
+        let (protocol, start) = frame.iter().find_map(|&byte| detector.push(byte)).unwrap();
+        prop_assert_eq!(protocol, Protocol::Protobuf);
+        prop_assert!(frame.starts_with(&start));
+    }
+
+    #[test]
+    fn control_is_applied_or_rejected(cmd in control()) {
+        let request = QRequest {
+            id: 1,
+            op: Commands::Control as i32,
+            data: &encode(&cmd),
+            ..Default::default()
+        };
+        let mut device = FullDevice::default();
+        let mut data = [0u8; MAX_RESPONSE_DATA];
+        let mut response = QResponse::default();
+        let result = poll_once(process_request(&mut device, &request, &mut response, &mut data));
+        // the response borrows the device
+        drop(response);
+
+        let (max, scale) = if cmd.permille { (1000, 1) } else { (100, 10) };
+        let valid = (0..=max).contains(&cmd.pwm1) && (0..=max).contains(&cmd.pwm2);
+        prop_assert_eq!(result.is_ok(), valid);
+        if valid {
+            prop_assert_eq!(
+                device.pwm,
+                ((cmd.pwm1 * scale) as u16, (cmd.pwm2 * scale) as u16)
+            );
+        } else {
+            prop_assert_eq!(device.pwm, (0, 0));
+        }
+    }
+}
+
+#[test]
+fn largest_responses_fit() {
+    // the requests answered with the most data
+    for op in [
+        Commands::Status,
+        Commands::Sensors,
+        Commands::I2cScan,
+        Commands::GetResetInfo,
+        Commands::GetHistory,
+    ] {
+        let op = op as i32;
+        let request = QRequest {
+            id: i32::MIN,
+            op,
+            data: &encode(&QHistoryRequest::default()),
+            ..Default::default()
+        };
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &encode(&request));
+        assert_eq!(decode(&bytes).error, Errors::None as i32, "op {}", op);
+    }
+
+    // and the same as revision 2 requests
+    for body in [
+        Body::status(QEmpty {}),
+        Body::sensors(QEmpty {}),
+        Body::i2c_scan(QEmpty {}),
+        Body::reset_info(QEmpty {}),
+        Body::history(QHistoryRequest::default()),
+    ] {
+        let request = QRequest {
+            id: i32::MIN,
+            body: body.clone(),
+            ..Default::default()
+        };
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &encode(&request));
+        let response = decode(&bytes);
+        assert_eq!(response.error, Errors::None as i32, "{:?}", body);
+        assert_ne!(response.body, Reply::None, "{:?}", body);
+    }
+}
+
+#[test]
+fn largest_batches_fit() {
+    // replies that don't fit are left out, as revision 1 and 2 requests
+    let ops = [
+        Operation::i2c_scan(QEmpty {}),
+        Operation::reset_info(QEmpty {}),
+    ]
+    .into_iter()
+    .cycle()
+    .take(8)
+    .map(|body| QOperation { body })
+    .collect();
+    let batch = QBatch { ops, atomic: true };
+    let data = encode(&batch);
+    for request in [
+        QRequest {
+            id: i32::MIN,
+            op: Commands::Batch as i32,
+            data: &data,
+            ..Default::default()
+        },
+        QRequest {
+            id: i32::MIN,
+            body: Body::batch(batch.clone()),
+            ..Default::default()
+        },
+    ] {
+        let mut device = FullDevice::default();
+        let bytes = check_frame(&mut device, &encode(&request));
+        let response = decode(&bytes);
+        assert_eq!(response.error, Errors::None as i32);
+
+        let reply = match response.body {
+            Reply::batch(reply) => reply,
+            _ => deserialize_from_slice::<QBatchReply>(response.data).unwrap(),
+        };
+        let results = &reply.results;
+        assert_eq!(results.len(), 8);
+        assert!(matches!(results[0].body, OperationReply::i2c_scan(_)));
+        // the rest only has room for the reset info
+        for result in &results[1..] {
+            let body = &result.body;
+            if result.error == Errors::None as i32 {
+                assert!(matches!(body, OperationReply::reset_info(_)));
+            } else {
+                assert_eq!(result.error, Errors::ErrorSerializingResponseData as i32);
+                assert_eq!(*body, OperationReply::None);
+            }
+        }
+    }
+}
+
+#[test]
+fn operations_get_a_result_when_replies_overflow() {
+    // status replies to fill the response, then a command after them
+    let control = QControl {
+        pwm1: 40,
+        pwm2: 60,
+        ..Default::default()
+    };
+    let ops = core::iter::repeat_n(Operation::status(QEmpty {}), 7)
+        .chain([Operation::control(control)])
+        .map(|body| QOperation { body })
+        .collect();
+    let request = QRequest {
+        id: 1,
+        body: Body::batch(QBatch { ops, atomic: false }),
+        ..Default::default()
+    };
+
+    let mut device = FullDevice::default();
+    let bytes = check_frame(&mut device, &encode(&request));
+    let Reply::batch(reply) = decode(&bytes).body else {
+        panic!("not a batch reply");
+    };
+    let results = &reply.results;
+    assert_eq!(results.len(), 8);
+    assert!(matches!(results[0].body, OperationReply::status(_)));
+    // the status ran but its reply was left out
+    assert_eq!(
+        results[6].error,
+        Errors::ErrorSerializingResponseData as i32
+    );
+    assert_eq!(results[6].body, OperationReply::None);
+    assert_eq!(results[7].error, Errors::None as i32);
+    assert_eq!(device.pwm, (400, 600));
+}
+
+#[test]
+fn errors_keep_the_request_id() {
+    let mut device = FullDevice::default();
+
+    // the id is read up to the malformed field
+    let bytes = check_frame(&mut device, &[3, 0x08, 9, 0x12, 0xff]);
+    let response = decode(&bytes);
+    assert_eq!(response.id, 9);
+    assert_eq!(response.error, Errors::ErrorDeserializingRequest as i32);
+
+    let bytes = check_frame(&mut device, &[3, 0x12, 0xff, 0x08, 9]);
+    assert_eq!(decode(&bytes).id, 0);
+}
+
+#[test]
+fn error_responses_fit() {
+    for code in 0..=Errors::Unsupported as i32 {
+        let error = Errors::from_i32(code).unwrap();
+        let response = error_response(i32::MIN, Error::field(error, "state_interval_ms"));
+        assert!(framed_size(&response) <= MAX_FRAME);
+        assert_eq!(decode(&encode(&response)), response);
+    }
+}
+
+#[test]
+fn heap_is_counted() {
+    let (_, heap) = peak_heap(|| vec![0u8; 1]);
+    assert_eq!(heap, 1);
+}
@@ -0,0 +1,45 @@
+use qaxe_core::events::{EventKind, EventLog};
+
+const ALL: u32 = u32::MAX;
+
+#[test]
+fn backlog_is_sent_once() {
+    let mut log = EventLog::<4>::new();
+    log.push(10, EventKind::Power, 0);
+    log.push(20, EventKind::Power, 1);
+
+    let event = log.next_unsent(ALL).unwrap();
+    assert_eq!((event.seq, event.value), (0, 0));
+    log.sent(&event);
+
+    let event = log.next_unsent(ALL).unwrap();
+    assert_eq!((event.seq, event.value), (1, 1));
+    log.sent(&event);
+
+    assert_eq!(log.next_unsent(ALL), None);
+}
+
+#[test]
+fn full_backlog_drops_the_oldest() {
+    let mut log = EventLog::<4>::new();
+    for i in 0..6 {
+        log.push(i, EventKind::Fault, i as i32);
+    }
+
+    // events 0 and 1 are lost, the host sees the gap in seq
+    let event = log.next_unsent(ALL).unwrap();
+    assert_eq!(event.seq, 2);
+}
+
+#[test]
+fn unsubscribed_kinds_are_skipped() {
+    let mut log = EventLog::<4>::new();
+    log.push(0, EventKind::Power, 1);
+    log.push(1, EventKind::Fault, 4);
+
+    let event = log.next_unsent(EventKind::Fault.mask()).unwrap();
+    assert_eq!(event.kind, EventKind::Fault);
+    log.sent(&event);
+
+    assert_eq!(log.next_unsent(ALL), None);
+}
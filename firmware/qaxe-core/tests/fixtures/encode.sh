@@ -0,0 +1,7 @@
+#!/bin/bash
+# Encodes the fixtures with protoc, the message is named in the first line
+cd "$(dirname "$0")"
+for text in *.txt; do
+    message=$(sed -n '1s/^# message: //p' "$text")
+    protoc --encode="$message" -I ../../src/protobuf coms.proto < "$text" > "${text%.txt}.bin"
+done
@@ -0,0 +1,141 @@
+use qaxe_core::framing::{Deframer, Detector, FrameError, Protocol};
+use qaxe_core::protobuf::coms::{QControl, QRequest};
+use qaxe_core::protobuf::deserialize_from_slice;
+
+fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::new();
+    let mut len = payload.len();
+    while len >= 0x80 {
+        frame.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    frame.push(len as u8);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+/// Feed the stream in packets of `packet` bytes and collect the results.
+fn deframe<const N: usize>(stream: &[u8], packet: usize) -> Vec<Result<Vec<u8>, FrameError>> {
+    let mut deframer = Deframer::<N>::new();
+    let mut frames = Vec::new();
+
+    for chunk in stream.chunks(packet) {
+        let mut data = chunk;
+        while !data.is_empty() {
+            let used = deframer.push(data);
+            data = &data[used..];
+            while let Some(result) = deframer.next_frame() {
+                frames.push(result.map(|f| f.to_vec()));
+            }
+        }
+    }
+    frames
+}
+
+#[test]
+fn split_frame_is_reassembled() {
+    let payload: Vec<u8> = (0..150).collect();
+    let stream = frame(&payload);
+
+    assert_eq!(deframe::<256>(&stream, 7), vec![Ok(stream.clone())]);
+    assert_eq!(deframe::<256>(&stream, 64), vec![Ok(stream)]);
+}
+
+#[test]
+fn concatenated_frames_are_split() {
+    let first = frame(&[1, 2, 3]);
+    let second = frame(&[]);
+    let third = frame(&[4; 70]);
+    let stream = [first.clone(), second.clone(), third.clone()].concat();
+
+    assert_eq!(
+        deframe::<256>(&stream, 64),
+        vec![Ok(first), Ok(second), Ok(third)]
+    );
+}
+
+#[test]
+fn oversized_frame_is_skipped() {
+    let big = frame(&[0xaa; 300]);
+    let next = frame(&[1, 2, 3]);
+    let stream = [big, next.clone()].concat();
+
+    assert_eq!(
+        deframe::<256>(&stream, 64),
+        vec![Err(FrameError::Oversized), Ok(next)]
+    );
+}
+
+#[test]
+fn invalid_prefix_is_dropped() {
+    let next = frame(&[1]);
+    let stream = [vec![0xff; 5], next.clone()].concat();
+
+    assert_eq!(
+        deframe::<256>(&stream, 5),
+        vec![Err(FrameError::InvalidLength), Ok(next)]
+    );
+}
+
+#[test]
+fn frame_filling_the_buffer() {
+    let stream = frame(&[7; 14]);
+
+    assert_eq!(
+        deframe::<15>(&[stream.clone(), stream.clone()].concat(), 64).len(),
+        2
+    );
+}
+
+#[test]
+fn malformed_messages_are_rejected() {
+    // a varint field running past the end of the message
+    let request = [8, 181, 128, 0, 0, 0, 0, 0, 193, 0];
+    assert!(deserialize_from_slice::<QRequest>(&request).is_err());
+    // a length prefix or bytes field reaching past the buffer
+    assert!(deserialize_from_slice::<QRequest>(&[9, 8, 1]).is_err());
+    assert!(deserialize_from_slice::<QRequest>(&[3, 26, 5, 1]).is_err());
+
+    let control = frame(&[16, 50, 24, 50, 32, 1]);
+    let cmd: QControl = deserialize_from_slice(&control).unwrap();
+    assert_eq!((cmd.pwm1, cmd.pwm2, cmd.permille), (50, 50, true));
+}
+
+/// Feed bytes until the protocol is known.
+fn detect(stream: &[u8]) -> Option<(Protocol, Vec<u8>)> {
+    let mut detector = Detector::new();
+    stream
+        .iter()
+        .find_map(|&byte| detector.push(byte))
+        .map(|(protocol, start)| (protocol, start.to_vec()))
+}
+
+#[test]
+fn protocol_is_detected() {
+    assert_eq!(detect(b"help\r"), Some((Protocol::Shell, b"he".to_vec())));
+    assert_eq!(detect(b"\r\n"), Some((Protocol::Shell, b"\r\n".to_vec())));
+    assert_eq!(detect(b"s"), None);
+    assert_eq!(
+        detect(br#"{"jsonrpc": "2.0"}"#),
+        Some((Protocol::JsonRpc, b"{\"".to_vec()))
+    );
+    // batches are JSON-RPC too, to be told they aren't supported
+    assert_eq!(
+        detect(br#"[{"jsonrpc": "2.0"}]"#),
+        Some((Protocol::JsonRpc, b"[{".to_vec()))
+    );
+
+    // a frame of 115 bytes is no 's'
+    assert_eq!(
+        detect(&[115, 8, 1]),
+        Some((Protocol::Protobuf, vec![115, 8]))
+    );
+    // an empty request is a frame on its own
+    assert_eq!(detect(&[0]), Some((Protocol::Protobuf, vec![0])));
+    assert_eq!(detect(&[0x92, 1]), Some((Protocol::Protobuf, vec![0x92])));
+    // a frame of 123 bytes is no '{'
+    assert_eq!(
+        detect(&[b'{', 8]),
+        Some((Protocol::Protobuf, vec![b'{', 8]))
+    );
+}
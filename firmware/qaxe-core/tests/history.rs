@@ -0,0 +1,61 @@
+use qaxe_core::history::{History, Sample};
+use qaxe_core::rpc::{framed_size, MAX_RESPONSE_DATA};
+
+fn sample(temp1: i32, temp2: i32, duty: u16) -> Sample {
+    Sample {
+        temps: [temp1, temp2],
+        duties: [duty, duty],
+    }
+}
+
+#[test]
+fn interval_summary() {
+    let mut history = History::<4>::new();
+    history.add_sample(sample(40 * 16, -5 * 16, 200));
+    history.add_sample(sample(50 * 16, -3 * 16, 400));
+    history.add_pgood_loss();
+    history.add_faults(1 << 2);
+    history.add_faults(1 << 6);
+
+    let interval = history.close(60_000);
+    assert_eq!(interval.samples, 2);
+    assert_eq!(interval.temp_min, [40 * 16, -5 * 16]);
+    assert_eq!(interval.temp_max, [50 * 16, -3 * 16]);
+    assert_eq!(interval.temp_avg, [45 * 16, -4 * 16]);
+    assert_eq!(interval.duty_avg, [300, 300]);
+    assert_eq!(interval.pgood_losses, 1);
+    assert_eq!(interval.faults, (1 << 2) | (1 << 6));
+
+    // the next interval starts empty
+    let interval = history.close(120_000);
+    assert_eq!((interval.seq, interval.samples, interval.faults), (1, 0, 0));
+}
+
+#[test]
+fn pages_fit_the_response() {
+    let mut history = History::<32>::new();
+    for i in 0..40 {
+        // negative values take the most space
+        history.add_sample(sample(-1, -1, 1000));
+        history.add_faults(u32::MAX);
+        history.close(i * 60_000);
+    }
+
+    // the oldest 8 intervals were dropped
+    let mut start = 0;
+    let mut seqs = Vec::new();
+    loop {
+        let page = history.page(start, 0, MAX_RESPONSE_DATA);
+        assert!(framed_size(&page) <= MAX_RESPONSE_DATA);
+        if page.intervals.is_empty() {
+            break;
+        }
+        seqs.extend(page.intervals.iter().map(|i| i.seq));
+        start = page.next as u32;
+    }
+    assert_eq!(seqs, (8..40).collect::<Vec<_>>());
+
+    let page = history.page(30, 2, MAX_RESPONSE_DATA);
+    assert_eq!(page.intervals.len(), 2);
+    assert_eq!(page.next, 32);
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use common::harness::{poll_once, FullDevice};
+use qaxe_core::framing::{Detector, Protocol};
+use qaxe_core::jsonrpc::{JsonRpc, Output, MAX_LINE};
+use qaxe_core::rpc::Errors;
+
+/// Send `input` line by line, returns the response lines.
+fn send(device: &mut FullDevice, input: &str) -> String {
+    let mut rpc = JsonRpc::new();
+    let mut text = String::new();
+    for &byte in input.as_bytes() {
+        if rpc.push(byte) {
+            let mut out = Output::new();
+            poll_once(rpc.run(device, &mut out));
+            text.push_str(core::str::from_utf8(&out).unwrap());
+        }
+    }
+    text
+}
+
+fn call(device: &mut FullDevice, request: &str) -> String {
+    send(device, &format!("{}\n", request))
+}
+
+#[test]
+fn methods_are_commands() {
+    let mut device = FullDevice::default();
+    let request = r#"{"jsonrpc": "2.0", "method": "control", "params": {"pwm1": 600, "pwm2": 350, "permille": true}, "id": 1}"#;
+    assert_eq!(
+        call(&mut device, request),
+        "{\"jsonrpc\":\"2.0\",\"result\":null,\"id\":1}\n"
+    );
+    assert_eq!(device.pwm, (600, 350));
+
+    let response = call(
+        &mut device,
+        r#"{"jsonrpc":"2.0","method":"get_pwm_config","id":"config"}"#,
+    );
+    assert_eq!(
+        response,
+        "{\"jsonrpc\":\"2.0\",\"result\":{\"frequency\":25000,\"invert1\":false,\"invert2\":false,\
+         \"min_duty1\":0,\"min_duty2\":0,\"kick_duty\":0,\"kick_ms\":0,\"ramp_rate\":0,\"shutdown_on\":0,\
+         \"full_fans_on\":0},\"id\":\"config\"}\n"
+    );
+
+    let response = call(
+        &mut device,
+        r#"{"jsonrpc":"2.0","method":"i2c_read","params":{"address":72,"reg":1,"length":2},"id":-3}"#,
+    );
+    assert_eq!(
+        response,
+        "{\"jsonrpc\":\"2.0\",\"result\":{\"address\":72,\"reg\":1,\"length\":2,\"data\":[255,255]},\
+         \"id\":-3}\n"
+    );
+}
+
+#[test]
+fn notifications_get_no_response() {
+    let mut device = FullDevice::default();
+    let input =
+        "{\"jsonrpc\":\"2.0\",\"method\":\"control\",\"params\":{\"pwm1\":50,\"pwm2\":20}}\r\n\n";
+    assert_eq!(send(&mut device, input), "");
+    assert_eq!(device.pwm, (500, 200));
+}
+
+#[test]
+fn null_ids_are_answered() {
+    let mut device = FullDevice::default();
+    assert_eq!(
+        call(&mut device, r#"{"jsonrpc":"2.0","method":"nop","id":null}"#),
+        "{\"jsonrpc\":\"2.0\",\"result\":null,\"id\":null}\n"
+    );
+    assert_eq!(
+        call(&mut device, r#"{"jsonrpc":"2.0","method":"fly","id":null}"#),
+        "{\"jsonrpc\":\"2.0\",\"error\":{\"code\":-32601,\"message\":\"method not found\"},\
+         \"id\":null}\n"
+    );
+}
+
+#[test]
+fn errors_follow_the_spec() {
+    let mut device = FullDevice::default();
+    let error = |code: i32, message: &str, id: &str| {
+        format!(
+            "{{\"jsonrpc\":\"2.0\",\"error\":{{\"code\":{},\"message\":\"{}\"}},\"id\":{}}}\n",
+            code, message, id
+        )
+    };
+
+    assert_eq!(
+        call(&mut device, r#"{"jsonrpc":"2.0","method""#),
+        error(-32700, "parse error", "null")
+    );
+    assert_eq!(
+        call(&mut device, r#"{"method":"status","id":1}"#),
+        error(-32600, "invalid request", "1")
+    );
+    assert_eq!(
+        call(
+            &mut device,
+            r#"{"jsonrpc":"2.0","method":"status","id":"a\"b"}"#
+        ),
+        error(-32600, "invalid id", "null")
+    );
+    assert_eq!(
+        call(&mut device, r#"{"jsonrpc":"2.0","method":"fly","id":2}"#),
+        error(-32601, "method not found", "2")
+    );
+    assert_eq!(
+        call(
+            &mut device,
+            r#"{"jsonrpc":"2.0","method":"control","params":[1,2],"id":3}"#
+        ),
+        error(-32602, "invalid params", "3")
+    );
+    let long = format!("{}\n", "x".repeat(MAX_LINE + 1));
+    assert_eq!(
+        send(&mut device, &long),
+        error(-32600, "request too long", "null")
+    );
+}
+
+#[test]
+fn device_errors_keep_their_code() {
+    let mut device = FullDevice::default();
+    let request = r#"{"jsonrpc":"2.0","method":"control","params":{"pwm1":101},"id":4}"#;
+    assert_eq!(
+        call(&mut device, request),
+        format!(
+            "{{\"jsonrpc\":\"2.0\",\"error\":{{\"code\":{},\"message\":\"value out of range\",\
+             \"data\":{{\"field\":\"pwm1\"}}}},\"id\":4}}\n",
+            Errors::OutOfRange as i32
+        )
+    );
+}
+
+#[test]
+fn protobuf_only_methods_are_not_found() {
+    let mut device = FullDevice::default();
+    let error = |code: i32, message: &str, id: &str| {
+        format!(
+            "{{\"jsonrpc\":\"2.0\",\"error\":{{\"code\":{},\"message\":\"{}\"}},\"id\":{}}}\n",
+            code, message, id
+        )
+    };
+
+    assert_eq!(
+        call(
+            &mut device,
+            r#"{"jsonrpc":"2.0","method":"subscribe","params":{"events":1},"id":5}"#
+        ),
+        error(-32601, "streams need protobuf", "5")
+    );
+    assert_eq!(
+        call(
+            &mut device,
+            r#"{"jsonrpc":"2.0","method":"log_level","params":{"level":3},"id":6}"#
+        ),
+        error(-32601, "logs need protobuf", "6")
+    );
+    assert_eq!(
+        call(
+            &mut device,
+            r#"{"jsonrpc":"2.0","method":"batch","params":{"ops":[]},"id":7}"#
+        ),
+        error(-32601, "batches need protobuf", "7")
+    );
+    // batches of the specification get a single error, none of their
+    // requests run
+    assert_eq!(
+        call(
+            &mut device,
+            r#" [{"jsonrpc":"2.0","method":"control","params":{"pwm1":50},"id":8}]"#
+        ),
+        error(-32600, "batches need protobuf", "null")
+    );
+    assert_eq!(device.pwm, (0, 0));
+}
+
+#[test]
+fn largest_replies_fit() {
+    let mut device = FullDevice::default();
+    let methods = [
+        r#""status""#,
+        r#""sensors""#,
+        r#""i2c_scan""#,
+        r#""i2c_read","params":{"address":72,"length":32}"#,
+        r#""reset_info""#,
+        r#""history""#,
+        r#""history","params":{"count":48}"#,
+        r#""get_pwm_config""#,
+    ];
+    for method in methods {
+        let request = format!(r#"{{"jsonrpc":"2.0","method":{},"id":2147483647}}"#, method);
+        let response = call(&mut device, &request);
+        assert!(
+            response.contains("\"result\":") && response.ends_with(",\"id\":2147483647}\n"),
+            "{}: {}",
+            method,
+            response
+        );
+    }
+}
+
+#[test]
+fn json_rpc_is_left_after_a_reset() {
+    let mut device = FullDevice::default();
+    let mut detector = Detector::new();
+    let mut rpc = JsonRpc::new();
+
+    // a web tool went away in the middle of a request
+    let detected = b"{\"".iter().find_map(|&byte| detector.push(byte));
+    assert_eq!(
+        detected.map(|(protocol, _)| protocol),
+        Some(Protocol::JsonRpc)
+    );
+    for &byte in br#"{"jsonrpc":"2.0","method":"control","params":{"pwm1":50"# {
+        rpc.push(byte);
+    }
+    assert!(rpc.pending());
+
+    detector.clear();
+    rpc.clear();
+    assert!(!rpc.pending());
+    let detected = [2u8, 0x08, 1].iter().find_map(|&byte| detector.push(byte));
+    assert_eq!(
+        detected.map(|(protocol, _)| protocol),
+        Some(Protocol::Protobuf)
+    );
+    // the next line isn't appended to the dropped one
+    for &byte in br#"{"jsonrpc":"2.0","method":"nop","id":1}"# {
+        rpc.push(byte);
+    }
+    assert!(rpc.push(b'\n'));
+    let mut out = Output::new();
+    poll_once(rpc.run(&mut device, &mut out));
+    assert_eq!(out, b"{\"jsonrpc\":\"2.0\",\"result\":null,\"id\":1}\n"[..]);
+    assert_eq!(device.pwm, (0, 0));
+}
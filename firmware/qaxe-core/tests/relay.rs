@@ -0,0 +1,128 @@
+use qaxe_core::relay::{valid_baud, ResponseSync, RESPONSE_LEN};
+use qaxe_core::sim::{
+    command, crc5, job, response_valid, Chain, Model, CMD_INACTIVE, CMD_READ, CMD_SET_ADDRESS,
+    CMD_WRITE, DEFAULT_BAUD, FAST_BAUD, FAST_UART_1M, GROUP_ALL, REG_CHIP_ID, REG_FAST_UART,
+    TYPE_CMD,
+};
+
+/// Relay the chain output through the response sync like the firmware does.
+fn relay(chain: &mut Chain, baud: u32, noise: &[u8]) -> Vec<[u8; RESPONSE_LEN]> {
+    let mut sync = ResponseSync::new();
+    noise
+        .iter()
+        .copied()
+        .chain(chain.transmit(baud))
+        .filter_map(|byte| sync.push(byte))
+        .collect()
+}
+
+fn read_chip_ids(chain: &mut Chain, baud: u32) -> Vec<[u8; RESPONSE_LEN]> {
+    chain.receive(
+        baud,
+        &command(TYPE_CMD | GROUP_ALL | CMD_READ, &[0, REG_CHIP_ID]),
+    );
+    relay(chain, baud, &[])
+}
+
+#[test]
+fn crc5_matches_known_command() {
+    // chip id read as sent by the mining software
+    assert_eq!(
+        command(TYPE_CMD | GROUP_ALL | CMD_READ, &[0x00, 0x00]),
+        [0x55, 0xaa, 0x52, 0x05, 0x00, 0x00, 0x0a]
+    );
+    assert_eq!(crc5(&[0x52, 0x05, 0x00, 0x00]), 0x0a);
+}
+
+#[test]
+fn chips_are_enumerated() {
+    let mut chain = Chain::new(Model::Bm1370, 4);
+
+    let ids = read_chip_ids(&mut chain, DEFAULT_BAUD);
+    assert_eq!(ids.len(), 4);
+    for id in &ids {
+        assert!(response_valid(id));
+        assert_eq!(id[2..4], [0x13, 0x70]);
+    }
+
+    chain.receive(
+        DEFAULT_BAUD,
+        &command(TYPE_CMD | GROUP_ALL | CMD_INACTIVE, &[0, 0]),
+    );
+    for i in 0..4 {
+        chain.receive(
+            DEFAULT_BAUD,
+            &command(TYPE_CMD | CMD_SET_ADDRESS, &[i * 8, 0]),
+        );
+    }
+    assert_eq!(chain.addresses(), [Some(0), Some(8), Some(16), Some(24)]);
+
+    let ids = read_chip_ids(&mut chain, DEFAULT_BAUD);
+    let addresses: Vec<u8> = ids.iter().map(|id| id[6]).collect();
+    assert_eq!(addresses, [0, 8, 16, 24]);
+}
+
+#[test]
+fn jobs_are_answered_with_nonces() {
+    let mut chain = Chain::new(Model::Bm1366, 1);
+    chain.set_nonces_per_job(3);
+
+    let mut data = [0u8; 82];
+    data[0] = 0x28;
+    chain.receive(DEFAULT_BAUD, &job(&data));
+
+    let nonces = relay(&mut chain, DEFAULT_BAUD, &[]);
+    assert_eq!(nonces.len(), 3);
+    for nonce in &nonces {
+        assert!(response_valid(nonce));
+        assert_eq!(nonce[7], 0x28);
+        assert_eq!(nonce[10] & 0x80, 0x80);
+    }
+
+    // a corrupted job is dropped
+    let mut packet = job(&data);
+    packet[10] ^= 1;
+    chain.receive(DEFAULT_BAUD, &packet);
+    assert!(relay(&mut chain, DEFAULT_BAUD, &[]).is_empty());
+    assert_eq!(chain.errors, 1);
+}
+
+#[test]
+fn relay_resyncs_after_noise() {
+    let mut chain = Chain::new(Model::Bm1368, 2);
+    chain.receive(
+        DEFAULT_BAUD,
+        &command(TYPE_CMD | GROUP_ALL | CMD_READ, &[0, REG_CHIP_ID]),
+    );
+
+    // a half response, a lone 0xaa and a broken preamble in front
+    let noise = [0x55, 0x13, 0xaa, 0x00, 0xaa, 0xaa];
+    let responses = relay(&mut chain, DEFAULT_BAUD, &noise);
+    assert_eq!(responses.len(), 2);
+    assert!(responses.iter().all(response_valid));
+}
+
+#[test]
+fn relay_follows_the_baud_switch() {
+    let mut chain = Chain::new(Model::Bm1366, 1);
+
+    let fast_uart = FAST_UART_1M.to_be_bytes();
+    let mut data = vec![0, REG_FAST_UART];
+    data.extend_from_slice(&fast_uart);
+    chain.receive(
+        DEFAULT_BAUD,
+        &command(TYPE_CMD | GROUP_ALL | CMD_WRITE, &data),
+    );
+    assert_eq!(chain.baud(), FAST_BAUD);
+
+    // a relay staying at the old rate loses the chain
+    assert!(read_chip_ids(&mut chain, DEFAULT_BAUD).is_empty());
+
+    // the host switched its line coding, the relay takes it over
+    let baud = valid_baud(FAST_BAUD).unwrap();
+    let ids = read_chip_ids(&mut chain, baud);
+    assert_eq!(ids.len(), 1);
+    assert!(response_valid(&ids[0]));
+
+    assert_eq!(valid_baud(4_000_000), None);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::strategies::*;
+use proptest::prelude::*;
+use qaxe_core::protobuf::coms::{
+    QBatch, QBatchReply, QBringUp, QControl, QEmpty, QError, QEvent, QHistory, QHistoryInterval,
+    QHistoryRequest, QI2cScan, QI2cTransfer, QLog, QLogLevel, QOperation, QPwmConfig, QRequest,
+    QResetInfo, QResponse, QResult, QSensor, QSensors, QState, QSubscribe,
+};
+use qaxe_core::protobuf::deserialize_from_slice;
+
+/// Every message decodes to what was encoded, `encode` checks `get_size`.
+macro_rules! roundtrip {
+    ($($test:ident: $message:ty = $strategy:expr;)*) => {
+        proptest! {
+            $(
+                #[test]
+                fn $test(message in $strategy) {
+                    let bytes = encode(&message);
+                    let decoded: $message = deserialize_from_slice(&bytes).unwrap();
+                    prop_assert_eq!(decoded, message);
+                }
+            )*
+        }
+    };
+}
+
+roundtrip! {
+    request_roundtrip: QRequest = request();
+    response_roundtrip: QResponse = response();
+    error_roundtrip: QError = error();
+    control_roundtrip: QControl = control();
+    state_roundtrip: QState = state();
+    sensor_roundtrip: QSensor = sensor();
+    sensors_roundtrip: QSensors = sensors();
+    i2c_scan_roundtrip: QI2cScan = i2c_scan();
+    i2c_transfer_roundtrip: QI2cTransfer = i2c_transfer();
+    bring_up_roundtrip: QBringUp = bring_up();
+    pwm_config_roundtrip: QPwmConfig = pwm_config();
+    reset_info_roundtrip: QResetInfo = reset_info();
+    event_roundtrip: QEvent = event();
+    subscribe_roundtrip: QSubscribe = subscribe();
+    history_request_roundtrip: QHistoryRequest = history_request();
+    history_interval_roundtrip: QHistoryInterval = history_interval();
+    history_roundtrip: QHistory = history();
+    log_level_roundtrip: QLogLevel = log_level();
+    log_roundtrip: QLog = log();
+    empty_roundtrip: QEmpty = Just(QEmpty {});
+    operation_roundtrip: QOperation = operation();
+    batch_roundtrip: QBatch = batch();
+    result_roundtrip: QResult = result();
+    batch_reply_roundtrip: QBatchReply = batch_reply();
+}
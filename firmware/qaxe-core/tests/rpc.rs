@@ -0,0 +1,409 @@
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use heapless::Vec;
+use qaxe_core::protobuf::coms::{
+    QBatch, QControl, QEmpty, QHistoryRequest, QLogLevel, QOperation, QPwmConfig, QRequest,
+    QResetInfo, QResponse, QSensors, QState, QSubscribe,
+};
+use qaxe_core::protobuf::wire::MessageWrite;
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core::rpc::{
+    error_response, process_request, request_body, state_response, Body, Commands, Device, Error,
+    Errors, Operation, OperationReply, Reply, MAX_RESPONSE_DATA,
+};
+
+/// A board whose PWM task never gets to run, setpoints pile up in the signal
+/// like they do on the firmware while the fans are ramping or kick-starting.
+struct StalledPwm {
+    target: Signal<CriticalSectionRawMutex, [u16; 2]>,
+}
+
+impl Device for StalledPwm {
+    fn set_pwm(&mut self, pwm1: u16, pwm2: u16) {
+        self.target.signal([pwm1, pwm2]);
+    }
+
+    fn set_pwm_config(&mut self, _config: &QPwmConfig) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn reset(&mut self) {}
+
+    fn shutdown(&mut self) {}
+
+    async fn status(&mut self) -> QState {
+        QState {
+            pgood_1v2: 1,
+            ..Default::default()
+        }
+    }
+
+    async fn sensors(&mut self) -> QSensors {
+        QSensors::default()
+    }
+
+    async fn reset_info(&mut self) -> QResetInfo<'_> {
+        QResetInfo::default()
+    }
+}
+
+fn request(id: i32, op: Commands, data: Vec<u8, 16>) -> QRequest<'static> {
+    QRequest {
+        id,
+        op: op as i32,
+        // the few bytes leaked keep the helpers simple
+        data: data.to_vec().leak(),
+        ..Default::default()
+    }
+}
+
+fn encode<M: MessageWrite>(cmd: &M) -> Vec<u8, 16> {
+    let mut data = [0u8; 16];
+    let len = serialize_into_slice(cmd, &mut data).unwrap();
+    Vec::from_slice(&data[..len]).unwrap()
+}
+
+fn control(pwm1: i32, pwm2: i32) -> Vec<u8, 16> {
+    encode(&QControl {
+        pwm1,
+        pwm2,
+        permille: true,
+        ..Default::default()
+    })
+}
+
+/// Poll a request exactly once, it has to complete without waiting.
+fn poll_once(device: &mut StalledPwm, request: &QRequest) -> Result<usize, Error> {
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    poll_once_with(process_request(device, request, &mut response, &mut data))
+}
+
+fn poll_once_with<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(result) => result,
+        Poll::Pending => panic!("request blocked"),
+    }
+}
+
+#[test]
+fn control_bursts_do_not_block_status() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    for id in 0..100 {
+        let req = request(id, Commands::Control, control(id * 10, 1000 - id * 10));
+        assert_eq!(poll_once(&mut device, &req), Ok(0));
+    }
+
+    let status = request(100, Commands::Status, Vec::new());
+    assert!(poll_once(&mut device, &status).unwrap() > 0);
+
+    // only the latest setpoint is left for the PWM task
+    assert_eq!(device.target.try_take(), Some([990, 10]));
+    assert_eq!(device.target.try_take(), None);
+}
+
+#[test]
+fn control_out_of_range_is_rejected() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    let req = request(1, Commands::Control, control(1001, 0));
+    assert_eq!(
+        poll_once(&mut device, &req),
+        Err(Error::field(Errors::OutOfRange, "pwm1"))
+    );
+    assert_eq!(device.target.try_take(), None);
+}
+
+#[test]
+fn errors_carry_details() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    let req = request(1, Commands::I2cScan, Vec::new());
+    let error = poll_once(&mut device, &req).unwrap_err();
+    assert_eq!(error, Errors::Unsupported.into());
+
+    let response = error_response(7, Error::field(Errors::OutOfRange, "pwm2"));
+    assert_eq!(response.error, Errors::OutOfRange as i32);
+    let detail = response.detail.unwrap();
+    assert_eq!(Errors::from_i32(detail.code), Some(Errors::OutOfRange));
+    assert_eq!(detail.reason, "value out of range");
+    assert_eq!(detail.field, "pwm2");
+}
+
+#[test]
+fn state_stream_interval_is_checked() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    let subscribe = QSubscribe {
+        events: 0,
+        state_interval_ms: 10,
+    };
+    let req = request(1, Commands::Subscribe, encode(&subscribe));
+    assert_eq!(
+        poll_once(&mut device, &req),
+        Err(Error::field(Errors::OutOfRange, "state_interval_ms"))
+    );
+
+    // a streamed snapshot isn't a reply to any request
+    let response = state_response(QState {
+        timestamp_us: 1 << 40,
+        ..Default::default()
+    });
+    assert_eq!((response.id, response.error), (0, 0));
+    let mut buf = [0u8; 64];
+    serialize_into_slice(&response, &mut buf).unwrap();
+    let decoded: QResponse = deserialize_from_slice(&buf).unwrap();
+    assert_eq!(decoded.state.unwrap().timestamp_us, 1 << 40);
+}
+
+#[test]
+fn log_level_is_checked() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    let req = request(1, Commands::SetLogLevel, encode(&QLogLevel { level: 6 }));
+    assert_eq!(
+        poll_once(&mut device, &req),
+        Err(Error::field(Errors::OutOfRange, "level"))
+    );
+
+    // a board without log forwarding
+    let req = request(2, Commands::SetLogLevel, encode(&QLogLevel { level: 3 }));
+    assert_eq!(
+        poll_once(&mut device, &req),
+        Err(Errors::Unsupported.into())
+    );
+}
+
+#[test]
+fn history_request_is_checked() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+
+    for (start, count, field) in [(-1, 0, "start"), (i32::MIN, 3, "start"), (0, -1, "count")] {
+        let req = request(
+            1,
+            Commands::GetHistory,
+            encode(&QHistoryRequest { start, count }),
+        );
+        assert_eq!(
+            poll_once(&mut device, &req),
+            Err(Error::field(Errors::OutOfRange, field))
+        );
+    }
+}
+
+#[test]
+fn revision_1_requests_become_bodies() {
+    let req = request(1, Commands::Control, control(500, 600));
+    let expected = QControl {
+        pwm1: 500,
+        pwm2: 600,
+        permille: true,
+        ..Default::default()
+    };
+    assert_eq!(request_body(&req), Ok(Body::control(expected)));
+    assert_eq!(
+        request_body(&request(1, Commands::Status, Vec::new())),
+        Ok(Body::status(QEmpty {}))
+    );
+
+    let mut req = request(1, Commands::Status, Vec::new());
+    req.op = 99;
+    assert_eq!(
+        request_body(&req),
+        Err(Error::field(Errors::InvalidCommand, "op"))
+    );
+
+    let req = request(1, Commands::Control, Vec::from_slice(&[5, 0xff]).unwrap());
+    assert_eq!(
+        request_body(&req),
+        Err(Error::field(Errors::ErrorDeserializingRequestData, "data"))
+    );
+
+    // the body wins over op
+    let mut req = request(1, Commands::Reset, Vec::new());
+    req.body = Body::status(QEmpty {});
+    assert_eq!(request_body(&req), Ok(Body::status(QEmpty {})));
+}
+
+#[test]
+fn revisions_get_the_same_replies() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+    let state = QState {
+        pgood_1v2: 1,
+        ..Default::default()
+    };
+
+    // revision 1, the state in data
+    let req = request(1, Commands::Status, Vec::new());
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    let result = poll_once_with(process_request(&mut device, &req, &mut response, &mut data));
+    assert!(result.unwrap() > 0);
+    assert_eq!(response.id, 1);
+    assert_eq!(response.body, Reply::None);
+    assert_eq!(
+        deserialize_from_slice::<QState>(response.data),
+        Ok(state.clone())
+    );
+    drop(response);
+
+    // revision 2, the state in the body
+    let req = QRequest {
+        id: 2,
+        body: Body::status(QEmpty {}),
+        ..Default::default()
+    };
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    let result = poll_once_with(process_request(&mut device, &req, &mut response, &mut data));
+    assert_eq!(result, Ok(0));
+    assert_eq!(response.id, 2);
+    assert!(response.data.is_empty());
+    assert_eq!(response.body, Reply::status(state));
+    drop(response);
+
+    // and validated the same way
+    let req = QRequest {
+        id: 3,
+        body: Body::control(QControl {
+            pwm1: 1001,
+            permille: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        poll_once(&mut device, &req),
+        Err(Error::field(Errors::OutOfRange, "pwm1"))
+    );
+}
+
+fn batch(atomic: bool) -> QRequest<'static> {
+    let control = |pwm1| {
+        Operation::control(QControl {
+            pwm1,
+            permille: true,
+            ..Default::default()
+        })
+    };
+    let ops = [
+        control(100),
+        Operation::status(QEmpty {}),
+        control(1001),
+        control(200),
+    ];
+    QRequest {
+        id: 1,
+        body: Body::batch(QBatch {
+            ops: ops.into_iter().map(|body| QOperation { body }).collect(),
+            atomic,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn batches_run_in_order() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    let result = poll_once_with(process_request(
+        &mut device,
+        &batch(false),
+        &mut response,
+        &mut data,
+    ));
+    assert_eq!(result, Ok(0));
+    // a failing operation doesn't fail the batch
+    assert_eq!(response.error, Errors::None as i32);
+
+    let Reply::batch(reply) = &response.body else {
+        panic!("not a batch reply: {:?}", response.body);
+    };
+    let errors: std::vec::Vec<_> = reply.results.iter().map(|r| r.error).collect();
+    let out_of_range = Errors::OutOfRange as i32;
+    assert_eq!(errors, [0, 0, out_of_range, 0]);
+    assert_eq!(reply.results[0].body, OperationReply::None);
+    assert!(matches!(reply.results[1].body, OperationReply::status(_)));
+    assert_eq!(reply.results[2].detail.as_ref().unwrap().field, "pwm1");
+    drop(response);
+
+    assert_eq!(device.target.try_take(), Some([200, 0]));
+}
+
+#[test]
+fn atomic_batches_stop_at_the_first_error() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    let result = poll_once_with(process_request(
+        &mut device,
+        &batch(true),
+        &mut response,
+        &mut data,
+    ));
+    assert_eq!(result, Ok(0));
+
+    let Reply::batch(reply) = &response.body else {
+        panic!("not a batch reply: {:?}", response.body);
+    };
+    let errors: std::vec::Vec<_> = reply.results.iter().map(|r| r.error).collect();
+    assert_eq!(errors, [0, 0, Errors::OutOfRange as i32]);
+    drop(response);
+
+    // the operations before the error stay applied
+    assert_eq!(device.target.try_take(), Some([100, 0]));
+}
+
+#[test]
+fn operations_without_a_command_are_rejected() {
+    let mut device = StalledPwm {
+        target: Signal::new(),
+    };
+    let req = QRequest {
+        id: 1,
+        body: Body::batch(QBatch {
+            ops: [QOperation::default()].into_iter().collect(),
+            atomic: false,
+        }),
+        ..Default::default()
+    };
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let mut response = QResponse::default();
+    let result = poll_once_with(process_request(&mut device, &req, &mut response, &mut data));
+    assert_eq!(result, Ok(0));
+
+    let Reply::batch(reply) = &response.body else {
+        panic!("not a batch reply: {:?}", response.body);
+    };
+    let result = &reply.results[0];
+    assert_eq!(result.error, Errors::InvalidCommand as i32);
+    assert_eq!(result.detail.as_ref().unwrap().field, "body");
+}
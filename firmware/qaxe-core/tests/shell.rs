@@ -0,0 +1,166 @@
+mod common;
+
+use common::harness::{poll_once, FullDevice};
+use qaxe_core::framing::{Detector, Protocol};
+use qaxe_core::shell::{Output, Shell, PROMPT};
+
+/// Type `input` into the shell, returns the echo and the output of the
+/// commands.
+fn type_in(shell: &mut Shell, device: &mut FullDevice, input: &str) -> String {
+    let mut text = String::new();
+    for &byte in input.as_bytes() {
+        let mut out = Output::new();
+        if shell.push(byte, &mut out) {
+            text.push_str(&out);
+            out.clear();
+            poll_once(shell.run(device, &mut out));
+        }
+        text.push_str(&out);
+    }
+    text
+}
+
+fn run(device: &mut FullDevice, line: &str) -> String {
+    let output = type_in(&mut Shell::new(), device, &format!("{}\r\n", line));
+    // without the echo and the prompt
+    let output = output.strip_prefix(&format!("{}\r\n", line)).unwrap();
+    output.strip_suffix(PROMPT).unwrap().to_string()
+}
+
+#[test]
+fn lines_are_echoed_and_edited() {
+    let mut device = FullDevice::default();
+    let mut shell = Shell::new();
+
+    let output = type_in(&mut shell, &mut device, "fam\x08n 1 60\r\n");
+    assert_eq!(output, "fam\x08 \x08n 1 60\r\nfan1 set to 60.0%\r\nqaxe> ");
+    assert_eq!(device.pwm, (600, 1000));
+
+    // an empty line only prompts again
+    assert_eq!(type_in(&mut shell, &mut device, "\r"), "\r\nqaxe> ");
+}
+
+#[test]
+fn fans_keep_the_other_setpoint() {
+    let mut device = FullDevice::default();
+    assert_eq!(run(&mut device, "fan 2 35"), "fan2 set to 35.0%\r\n");
+    // the device reports both targets at full speed
+    assert_eq!(device.pwm, (1000, 350));
+
+    assert_eq!(
+        run(&mut device, "fan 3 50"),
+        "error: value out of range (fan)\r\n"
+    );
+    assert_eq!(
+        run(&mut device, "fan 1 101"),
+        "error: value out of range (pwm1)\r\n"
+    );
+    assert_eq!(
+        run(&mut device, "fan 1 fast"),
+        "error: invalid parameter (percent)\r\n"
+    );
+}
+
+#[test]
+fn config_is_changed_by_field() {
+    let mut device = FullDevice::default();
+    let output = run(&mut device, "config set ramp_rate 200");
+    assert!(output.contains("frequency    25000\r\n"), "{}", output);
+    assert!(output.contains("ramp_rate    200\r\n"), "{}", output);
+    assert_eq!(device.config.frequency, 25_000);
+    assert_eq!(device.config.ramp_rate, 200);
+
+    assert_eq!(run(&mut device, "config get"), output);
+    run(&mut device, "config set shutdown_on 4");
+    assert_eq!(device.config.shutdown_on, 4);
+    assert_eq!(
+        run(&mut device, "config set invert1 2"),
+        "error: value out of range (invert1)\r\n"
+    );
+    assert_eq!(
+        run(&mut device, "config set speed 2"),
+        "error: invalid parameter (key)\r\n"
+    );
+}
+
+#[test]
+fn status_is_readable() {
+    let mut device = FullDevice::default();
+    let output = run(&mut device, "status");
+    assert!(output.contains("power    good"), "{}", output);
+    assert!(output.contains("temps    -40.0 C, -40.0 C"), "{}", output);
+    assert!(
+        output.contains("fan1     100.0% of 100.0%, 20000 rpm"),
+        "{}",
+        output
+    );
+
+    let output = run(&mut device, "temps");
+    assert!(output.starts_with("board    -40.0 C"), "{}", output);
+    assert_eq!(output.matches("0x4f     125.0 C, INA226 failed").count(), 4);
+}
+
+#[test]
+fn commands_are_checked() {
+    let mut device = FullDevice::default();
+    assert!(run(&mut device, "help").contains("config set <key> <n>"));
+    assert_eq!(
+        run(&mut device, "status now"),
+        "error: invalid command, try help\r\n"
+    );
+
+    // the echo stops at the longest line
+    let line = "x".repeat(80);
+    let output = type_in(&mut Shell::new(), &mut device, &format!("{}\r", line));
+    assert_eq!(
+        output,
+        format!(
+            "{}\r\nerror: value out of range (line)\r\nqaxe> ",
+            &line[..64]
+        )
+    );
+}
+
+#[test]
+fn exit_leaves_the_shell() {
+    let mut device = FullDevice::default();
+    let mut shell = Shell::new();
+    let mut out = Output::new();
+    for &byte in b"exit\r" {
+        shell.push(byte, &mut out);
+    }
+    assert!(!poll_once(shell.run(&mut device, &mut out)));
+    assert_eq!(out, "exit\r\n");
+}
+
+#[test]
+fn protocols_are_told_apart_after_a_reset() {
+    let mut device = FullDevice::default();
+    let mut detector = Detector::new();
+    let mut shell = Shell::new();
+
+    // a terminal closed in the middle of a line
+    let detected = b"fa".iter().find_map(|&byte| detector.push(byte));
+    assert_eq!(
+        detected.map(|(protocol, _)| protocol),
+        Some(Protocol::Shell)
+    );
+    type_in(&mut shell, &mut device, "fan 1");
+    assert!(shell.pending());
+
+    detector.clear();
+    shell.clear();
+    assert!(!shell.pending());
+    // the next host speaks protobuf, a frame of 2 bytes with the id
+    let detected = [2u8, 0x08, 1].iter().find_map(|&byte| detector.push(byte));
+    assert_eq!(
+        detected.map(|(protocol, _)| protocol),
+        Some(Protocol::Protobuf)
+    );
+    // and the shell starts on an empty line
+    assert_eq!(
+        type_in(&mut shell, &mut device, " 60\r"),
+        " 60\r\nerror: invalid command, try help\r\nqaxe> "
+    );
+    assert_eq!(device.pwm, (0, 0));
+}
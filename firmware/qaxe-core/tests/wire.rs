@@ -0,0 +1,193 @@
+use qaxe_core::protobuf::coms::{
+    QBatch, QBatchReply, QControl, QEmpty, QError, QEvent, QI2cScan, QI2cTransfer, QLog,
+    QOperation, QRequest, QResetInfo, QResponse, QResult, QSensor, QSensors, QState, QSubscribe,
+};
+use qaxe_core::protobuf::wire::{BytesReader, MessageRead, MessageWrite, Writer};
+use qaxe_core::rpc::{Body, Errors, Operation, OperationReply, Reply};
+
+// protoc writes messages without length prefix
+fn encode<M: MessageWrite>(message: &M) -> Vec<u8> {
+    let mut bytes = vec![0u8; message.get_size()];
+    let mut writer = Writer::new(&mut bytes);
+    message.write_message(&mut writer).unwrap();
+    assert_eq!(writer.len(), message.get_size());
+    bytes
+}
+
+fn decode<'a, M: MessageRead<'a>>(bytes: &'a [u8]) -> M {
+    M::from_reader(&mut BytesReader::from_bytes(bytes), bytes).unwrap()
+}
+
+/// Every message encodes to the bytes protoc makes of `fixtures/<test>.txt`
+/// and decodes from them, see `fixtures/encode.sh`.
+macro_rules! fixtures {
+    ($($test:ident: $message:ty = $value:expr;)*) => {$(
+        #[test]
+        fn $test() {
+            let bytes = include_bytes!(concat!("fixtures/", stringify!($test), ".bin"));
+            let message: $message = $value;
+            assert_eq!(encode(&message), bytes);
+            assert_eq!(decode::<$message>(bytes), message);
+        }
+    )*};
+}
+
+const OUT_OF_RANGE: QError = QError {
+    code: Errors::OutOfRange as i32,
+    reason: "value out of range",
+    field: "pwm1",
+};
+
+fixtures! {
+    request_control: QRequest = QRequest {
+        id: 1,
+        body: Body::control(QControl {
+            pwm1: 600,
+            pwm2: 600,
+            permille: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    request_revision_1: QRequest = QRequest {
+        id: -2,
+        op: 1,
+        data: &[0x10, 0x32, 0x18, 0x32],
+        ..Default::default()
+    };
+    request_i2c_write: QRequest = QRequest {
+        id: 3,
+        body: Body::i2c_write(QI2cTransfer {
+            address: 0x48,
+            reg: 1,
+            data: &[0x01, 0xff, 0x00],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    request_batch: QRequest = QRequest {
+        id: 4,
+        body: Body::batch(QBatch {
+            ops: [
+                Operation::control(QControl {
+                    pwm1: 40,
+                    pwm2: 60,
+                    ..Default::default()
+                }),
+                Operation::status(QEmpty {}),
+                Operation::subscribe(QSubscribe {
+                    events: 15,
+                    state_interval_ms: 1000,
+                }),
+            ]
+            .into_iter()
+            .map(|body| QOperation { body })
+            .collect(),
+            atomic: true,
+        }),
+        ..Default::default()
+    };
+    response_status: QResponse = QResponse {
+        id: 5,
+        body: Reply::status(QState {
+            pgood_1v2: 1,
+            temp1: 720,
+            temp2: -16,
+            vin_mv: 12034,
+            v1v2_mv: 1201,
+            vdda_mv: 3300,
+            mcu_temp: 560,
+            faults: 0,
+            sensors_failed: 2,
+            pwm1_applied: 450,
+            pwm2_applied: 1000,
+            pwm1_target: 600,
+            pwm2_target: 1000,
+            fan1_rpm: 2400,
+            fan2_rpm: 0,
+            timestamp_us: 123_456_789_012,
+        }),
+        ..Default::default()
+    };
+    response_error: QResponse = QResponse {
+        id: 6,
+        error: Errors::OutOfRange as i32,
+        detail: Some(OUT_OF_RANGE),
+        ..Default::default()
+    };
+    response_sensors: QResponse = QResponse {
+        id: 7,
+        body: Reply::sensors(QSensors {
+            sensors: [
+                QSensor {
+                    address: 0x48,
+                    kind: 1,
+                    temp: 640,
+                    health: 1,
+                    ..Default::default()
+                },
+                QSensor {
+                    address: 0x40,
+                    kind: 2,
+                    voltage_mv: 12010,
+                    current_ma: -250,
+                    power_mw: 3002,
+                    health: 2,
+                    errors: 3,
+                    ..Default::default()
+                },
+            ]
+            .into_iter()
+            .collect(),
+        }),
+        ..Default::default()
+    };
+    response_reset_info: QResponse = QResponse {
+        id: 8,
+        body: Reply::reset_info(QResetInfo {
+            cause: 0x108,
+            boots: 3,
+            panic: "temp1 > 110°C at src/bin/qaxe.rs:42",
+        }),
+        ..Default::default()
+    };
+    response_batch: QResponse = QResponse {
+        id: 9,
+        body: Reply::batch(QBatchReply {
+            results: [
+                QResult {
+                    body: OperationReply::i2c_scan(QI2cScan {
+                        addresses: &[0x40, 0x48, 0x49],
+                    }),
+                    ..Default::default()
+                },
+                QResult {
+                    error: Errors::OutOfRange as i32,
+                    detail: Some(OUT_OF_RANGE),
+                    ..Default::default()
+                },
+                QResult {
+                    error: Errors::ErrorSerializingResponseData as i32,
+                    ..Default::default()
+                },
+                QResult::default(),
+            ]
+            .into_iter()
+            .collect(),
+        }),
+        ..Default::default()
+    };
+    response_stream: QResponse = QResponse {
+        event: Some(QEvent {
+            seq: 17,
+            timestamp: 90_000,
+            kind: 1,
+            value: 4,
+        }),
+        log: Some(QLog {
+            data: &[0x02, 0x01, 0x00, 0x7f],
+            dropped: 12,
+        }),
+        ..Default::default()
+    };
+}
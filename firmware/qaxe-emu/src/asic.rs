@@ -0,0 +1,39 @@
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use qaxe_core::relay::{valid_baud, ResponseSync};
+
+use crate::board::Board;
+use crate::pty::{Pty, Wait};
+
+const POLL_MS: u16 = 10;
+
+/// Relay between the ASIC interface and the simulated chain like the
+/// firmware does between USB and USART1.
+pub fn serve(mut pty: Pty, board: Arc<Mutex<Board>>) -> io::Result<()> {
+    let mut sync = ResponseSync::new();
+    let mut buf = [0u8; 64];
+
+    loop {
+        let readable = pty.wait(POLL_MS)? == Wait::Readable;
+        let baud = valid_baud(pty.baud()?).unwrap_or(0);
+
+        let mut board = board.lock().unwrap();
+        if readable {
+            let n = pty.master.read(&mut buf)?;
+            // unpowered chips or chips in reset don't listen
+            if board.chips_running() {
+                board.chain().receive(baud, &buf[..n]);
+            }
+        }
+
+        let output = board.chain().transmit(baud);
+        drop(board);
+
+        for byte in output {
+            if let Some(response) = sync.push(byte) {
+                pty.master.write_all(&response)?;
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+
+use heapless::Vec as HVec;
+use qaxe_core::events::{EventKind, EventLog};
+use qaxe_core::protobuf::coms::{QPwmConfig, QResetInfo, QResponse, QSensor, QSensors, QState};
+use qaxe_core::rpc::{self, Device, Error};
+use qaxe_core::sim::Chain;
+
+const AMBIENT_C: f32 = 25.0;
+// rise over ambient with the ASICs running and the fans off
+const HEAT_RISE_C: f32 = 60.0;
+// part of the rise the fans take away at full speed
+const FAN_COOLING: f32 = 0.7;
+const THERMAL_TIME_CONSTANT_MS: f32 = 20_000.0;
+const MAX_RPM: u32 = 5_000;
+
+// the reset sequence of the firmware: LDOs after 250 ms, the buck 100 ms
+// later and the reset released another 250 ms later
+const PGOOD_AFTER_MS: u64 = 350;
+const RESET_RELEASED_AFTER_MS: u64 = 600;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rail {
+    Off,
+    Starting { since_ms: u64 },
+    On,
+}
+
+/// The simulated board behind both interfaces.
+///
+/// Time only moves with `tick`, so tests run it faster than real time.
+pub struct Board {
+    now_ms: u64,
+    rail: Rail,
+    pgood: bool,
+    duty: [u16; 2],
+    pwm_config: QPwmConfig,
+    temps: [f32; 2],
+    chain: Chain,
+    events: EventLog<16>,
+    subscribed: u32,
+    state_interval_ms: u64,
+    next_state_ms: Option<u64>,
+    unsolicited: VecDeque<QResponse<'static>>,
+}
+
+impl Board {
+    pub fn new(chain: Chain) -> Self {
+        Board {
+            now_ms: 0,
+            rail: Rail::Off,
+            pgood: false,
+            // the firmware starts with the fans at full speed
+            duty: [1000; 2],
+            pwm_config: QPwmConfig {
+                frequency: 10_000,
+                ..Default::default()
+            },
+            temps: [AMBIENT_C; 2],
+            chain,
+            events: EventLog::new(),
+            subscribed: 0,
+            state_interval_ms: 0,
+            next_state_ms: None,
+            unsolicited: VecDeque::new(),
+        }
+    }
+
+    /// Advance the simulation to `now_ms`.
+    pub fn tick(&mut self, now_ms: u64) {
+        let dt = now_ms.saturating_sub(self.now_ms) as f32;
+        self.now_ms = now_ms;
+
+        if let Rail::Starting { since_ms } = self.rail {
+            if now_ms - since_ms >= RESET_RELEASED_AFTER_MS {
+                self.rail = Rail::On;
+            }
+        }
+        let pgood = match self.rail {
+            Rail::Off => false,
+            Rail::Starting { since_ms } => now_ms - since_ms >= PGOOD_AFTER_MS,
+            Rail::On => true,
+        };
+        if pgood != self.pgood {
+            self.pgood = pgood;
+            self.push_event(EventKind::Power, pgood as i32);
+        }
+
+        let alpha = (dt / THERMAL_TIME_CONSTANT_MS).min(1.0);
+        for (temp, duty) in self.temps.iter_mut().zip(self.duty) {
+            let heat = if self.pgood { HEAT_RISE_C } else { 0.0 };
+            let cooling = 1.0 - FAN_COOLING * duty as f32 / 1000.0;
+            let target = AMBIENT_C + heat * cooling;
+            *temp += (target - *temp) * alpha;
+        }
+
+        self.queue_unsolicited();
+    }
+
+    /// True while the chips are powered and out of reset.
+    pub fn chips_running(&self) -> bool {
+        self.rail == Rail::On
+    }
+
+    pub fn chain(&mut self) -> &mut Chain {
+        &mut self.chain
+    }
+
+    /// Responses to send without a request, events and the status stream.
+    pub fn take_unsolicited(&mut self) -> Option<QResponse<'static>> {
+        self.unsolicited.pop_front()
+    }
+
+    fn push_event(&mut self, kind: EventKind, value: i32) {
+        self.events.push(self.now_ms as u32, kind, value);
+    }
+
+    fn queue_unsolicited(&mut self) {
+        if self.subscribed != 0 {
+            while let Some(event) = self.events.next_unsent(self.subscribed) {
+                self.unsolicited.push_back(event.to_response());
+                self.events.sent(&event);
+            }
+        }
+
+        if let Some(at) = self.next_state_ms {
+            if at <= self.now_ms {
+                self.next_state_ms = Some((at + self.state_interval_ms).max(self.now_ms));
+                let state = self.state();
+                self.unsolicited.push_back(rpc::state_response(state));
+            }
+        }
+    }
+
+    fn state(&self) -> QState {
+        let temp = |i: usize| (self.temps[i] * 16.0) as i32;
+        let rpm = |i: usize| (self.duty[i] as u32 * MAX_RPM / 1000) as i32;
+        QState {
+            pgood_1v2: self.pgood as i32,
+            temp1: temp(0),
+            temp2: temp(1),
+            vdda_mv: 3300,
+            mcu_temp: temp(0).max(temp(1)) / 2,
+            pwm1_applied: self.duty[0] as i32,
+            pwm2_applied: self.duty[1] as i32,
+            pwm1_target: self.duty[0] as i32,
+            pwm2_target: self.duty[1] as i32,
+            fan1_rpm: rpm(0),
+            fan2_rpm: rpm(1),
+            timestamp_us: self.now_ms * 1000,
+            ..Default::default()
+        }
+    }
+}
+
+impl Device for Board {
+    fn set_pwm(&mut self, pwm1: u16, pwm2: u16) {
+        self.duty = [pwm1, pwm2];
+    }
+
+    fn set_pwm_config(&mut self, config: &QPwmConfig) -> Result<(), Error> {
+        // only kept, the fans follow the duty right away without ramps or
+        // kick-starts
+        self.pwm_config = config.clone();
+        Ok(())
+    }
+
+    async fn pwm_config(&mut self) -> Result<QPwmConfig, Error> {
+        Ok(self.pwm_config.clone())
+    }
+
+    fn reset(&mut self) {
+        self.rail = Rail::Starting {
+            since_ms: self.now_ms,
+        };
+        self.chain.reset();
+        self.tick(self.now_ms);
+    }
+
+    fn shutdown(&mut self) {
+        self.rail = Rail::Off;
+        self.tick(self.now_ms);
+    }
+
+    async fn status(&mut self) -> QState {
+        self.state()
+    }
+
+    async fn sensors(&mut self) -> QSensors {
+        let sensors = [0x48, 0x4c]
+            .iter()
+            .zip(self.temps)
+            .map(|(&address, temp)| QSensor {
+                address,
+                kind: 1,
+                temp: (temp * 16.0) as i32,
+                health: 1,
+                ..Default::default()
+            })
+            .collect();
+        QSensors { sensors }
+    }
+
+    async fn i2c_scan(&mut self) -> Result<HVec<u8, 112>, Error> {
+        Ok(HVec::from_slice(&[0x48, 0x4c]).unwrap())
+    }
+
+    async fn reset_info(&mut self) -> QResetInfo<'_> {
+        QResetInfo {
+            cause: 1,
+            boots: 1,
+            panic: "",
+        }
+    }
+
+    fn subscribe(&mut self, events: u32, state_interval_ms: u32) -> Result<(), Error> {
+        self.subscribed = events;
+        self.state_interval_ms = state_interval_ms as u64;
+        self.next_state_ms = (state_interval_ms != 0).then_some(self.now_ms);
+        self.queue_unsolicited();
+        Ok(())
+    }
+}
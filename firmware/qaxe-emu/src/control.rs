@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::io::{self, Read, Write};
+use std::pin::pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+use qaxe_core::framing::{Deframer, Detector, FrameError, Protocol, MAX_FRAME};
+use qaxe_core::jsonrpc::{self, JsonRpc};
+use qaxe_core::protobuf::coms::QResponse;
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core::rpc::{self, error_response, Errors, MAX_RESPONSE_DATA};
+use qaxe_core::shell::{Output, Shell};
+
+use crate::board::Board;
+use crate::pty::{Pty, Wait};
+
+// how often the board is advanced while the host is quiet
+const TICK_MS: u16 = 50;
+
+/// Run a future of the board to completion, they never wait for anything.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+/// Decode and process one framed request like the firmware does.
+pub fn handle_frame<'b>(
+    board: &'b mut Board,
+    frame: &[u8],
+    data: &'b mut [u8; MAX_RESPONSE_DATA],
+) -> QResponse<'b> {
+    let request = match deserialize_from_slice(frame) {
+        Ok(request) => request,
+        Err(_) => {
+            return error_response(
+                rpc::request_id(frame),
+                Errors::ErrorDeserializingRequest.into(),
+            )
+        }
+    };
+
+    let mut response = QResponse::default();
+    if let Err(e) = block_on(rpc::process_request(board, &request, &mut response, data)) {
+        response = error_response(request.id, e);
+    }
+    response
+}
+
+fn write_response(port: &mut impl Write, response: &QResponse) -> io::Result<()> {
+    let mut bytes = [0u8; MAX_FRAME];
+    let len = match serialize_into_slice(response, &mut bytes) {
+        Ok(len) => len,
+        Err(e) => {
+            eprintln!("response {} not serialized: {:?}", response.id, e);
+            let error = error_response(response.id, Errors::ErrorSerializingResponseData.into());
+            // an error response always fits
+            serialize_into_slice(&error, &mut bytes).unwrap()
+        }
+    };
+    port.write_all(&bytes[..len])
+}
+
+/// The host side of the control interface, in the protocol it speaks.
+#[derive(Default)]
+pub struct Session {
+    detector: Detector,
+    protocol: Option<Protocol>,
+    deframer: Deframer<MAX_FRAME>,
+    shell: Shell,
+    json: JsonRpc,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    pub fn protocol(&self) -> Option<Protocol> {
+        self.protocol
+    }
+
+    /// Handle bytes received from the host, writing the replies to `port`.
+    pub fn receive(
+        &mut self,
+        port: &mut impl Write,
+        board: &mut Board,
+        mut data: &[u8],
+    ) -> io::Result<()> {
+        while !data.is_empty() {
+            let Some(protocol) = self.protocol else {
+                if let Some((protocol, start)) = self.detector.push(data[0]) {
+                    self.protocol = Some(protocol);
+                    self.receive(port, board, &start)?;
+                }
+                data = &data[1..];
+                continue;
+            };
+
+            data = match protocol {
+                Protocol::Protobuf => {
+                    let used = self.deframer.push(data);
+                    self.frames(port, board)?;
+                    &data[used..]
+                }
+                Protocol::Shell => self.lines(port, board, data)?,
+                Protocol::JsonRpc => {
+                    self.requests(port, board, data)?;
+                    &[]
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn frames(&mut self, port: &mut impl Write, board: &mut Board) -> io::Result<()> {
+        while let Some(frame) = self.deframer.next_frame() {
+            let mut response_data = [0u8; MAX_RESPONSE_DATA];
+            let response = match frame {
+                Ok(frame) => handle_frame(board, frame, &mut response_data),
+                Err(FrameError::Oversized) => error_response(0, Errors::OversizedFrame.into()),
+                Err(FrameError::InvalidLength) => {
+                    error_response(0, Errors::ErrorDeserializingRequest.into())
+                }
+            };
+            write_response(port, &response)?;
+        }
+        Ok(())
+    }
+
+    /// Run the typed lines, returns the bytes after an `exit`.
+    fn lines<'d>(
+        &mut self,
+        port: &mut impl Write,
+        board: &mut Board,
+        data: &'d [u8],
+    ) -> io::Result<&'d [u8]> {
+        let mut out = Output::new();
+        for (i, &byte) in data.iter().enumerate() {
+            if self.shell.push(byte, &mut out) {
+                port.write_all(out.as_bytes())?;
+                out.clear();
+                if !block_on(self.shell.run(board, &mut out)) {
+                    // the protocol is detected again
+                    self.protocol = None;
+                    return Ok(&data[i + 1..]);
+                }
+            }
+            port.write_all(out.as_bytes())?;
+            out.clear();
+        }
+        Ok(&[])
+    }
+
+    fn requests(
+        &mut self,
+        port: &mut impl Write,
+        board: &mut Board,
+        data: &[u8],
+    ) -> io::Result<()> {
+        for &byte in data {
+            if self.json.push(byte) {
+                let mut out = jsonrpc::Output::new();
+                block_on(self.json.run(board, &mut out));
+                if !out.is_empty() {
+                    port.write_all(&out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serve the control interface until the pty fails.
+pub fn serve(mut pty: Pty, board: Arc<Mutex<Board>>, start: Instant) -> io::Result<()> {
+    let mut session = Session::new();
+    let mut packet = [0u8; 64];
+
+    loop {
+        let wait = pty.wait(TICK_MS)?;
+        let mut board = board.lock().unwrap();
+        board.tick(start.elapsed().as_millis() as u64);
+
+        match wait {
+            Wait::Readable => {
+                let n = pty.master.read(&mut packet)?;
+                session.receive(&mut pty.master, &mut board, &packet[..n])?;
+            }
+            // the next host is told apart again, e.g. after the shell was
+            // closed without `exit`
+            Wait::Closed => session = Session::new(),
+            Wait::Timeout => (),
+        }
+
+        while let Some(response) = board.take_unsolicited() {
+            // binary messages would garble text, the host that subscribed
+            // may have left
+            if matches!(session.protocol(), None | Some(Protocol::Protobuf)) {
+                write_response(&mut pty.master, &response)?;
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Host emulator of a qaxe board for developing host software without one.
+
+pub mod asic;
+pub mod board;
+pub mod control;
+pub mod pty;
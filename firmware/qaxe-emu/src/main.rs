@@ -0,0 +1,67 @@
+use std::process::exit;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use qaxe_core::sim::{Chain, Model};
+use qaxe_emu::board::Board;
+use qaxe_emu::pty::Pty;
+use qaxe_emu::{asic, control};
+
+const USAGE: &str = "usage: qaxe-emu [--model bm1366|bm1368|bm1370] [--chips N]";
+
+fn parse_args() -> Option<(Model, usize)> {
+    let mut model = Model::Bm1366;
+    let mut chips = 4;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--model" => {
+                model = match args.next()?.as_str() {
+                    "bm1366" => Model::Bm1366,
+                    "bm1368" => Model::Bm1368,
+                    "bm1370" => Model::Bm1370,
+                    _ => return None,
+                }
+            }
+            "--chips" => chips = args.next()?.parse().ok()?,
+            _ => return None,
+        }
+    }
+    Some((model, chips))
+}
+
+fn main() {
+    let Some((model, chips)) = parse_args() else {
+        eprintln!("{}", USAGE);
+        exit(2);
+    };
+
+    let (control_pty, asic_pty) = match (Pty::open(), Pty::open()) {
+        (Ok(control), Ok(asic)) => (control, asic),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("can't open pty: {}", e);
+            exit(1);
+        }
+    };
+    println!("asic:    {}", asic_pty.path.display());
+    println!("control: {}", control_pty.path.display());
+    println!("{} x {:?}", chips, model);
+
+    let board = Arc::new(Mutex::new(Board::new(Chain::new(model, chips))));
+    let start = Instant::now();
+
+    let asic_board = board.clone();
+    thread::spawn(move || {
+        if let Err(e) = asic::serve(asic_pty, asic_board) {
+            eprintln!("asic interface: {}", e);
+            exit(1);
+        }
+    });
+
+    if let Err(e) = control::serve(control_pty, board, start) {
+        eprintln!("control interface: {}", e);
+        exit(1);
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::os::fd::AsFd;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+use nix::pty::openpty;
+use nix::sys::termios::{cfgetospeed, cfmakeraw, tcgetattr, tcsetattr, BaudRate, SetArg};
+use nix::unistd::ttyname;
+
+/// A pseudo-terminal standing in for one CDC interface.
+pub struct Pty {
+    pub master: File,
+    pub path: PathBuf,
+}
+
+/// What the host did while [`Pty::wait`] waited.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wait {
+    Readable,
+    Timeout,
+    /// No host has the port open, e.g. one closed it, like a DTR drop.
+    Closed,
+}
+
+impl Pty {
+    pub fn open() -> nix::Result<Self> {
+        let pty = openpty(None, None)?;
+
+        // a CDC interface passes bytes through unchanged
+        let mut termios = tcgetattr(&pty.slave)?;
+        cfmakeraw(&mut termios);
+        tcsetattr(&pty.slave, SetArg::TCSANOW, &termios)?;
+
+        // the slave is closed until a host opens it, the raw mode stays
+        Ok(Pty {
+            path: ttyname(&pty.slave)?,
+            master: pty.master.into(),
+        })
+    }
+
+    /// Wait up to `timeout_ms` for data from the host. Data sent before the
+    /// host closed the port is still readable.
+    pub fn wait(&self, timeout_ms: u16) -> nix::Result<Wait> {
+        let mut fds = [PollFd::new(self.master.as_fd(), PollFlags::POLLIN)];
+        poll(&mut fds, PollTimeout::from(timeout_ms))?;
+        let events = fds[0].revents().unwrap_or(PollFlags::empty());
+        if events.contains(PollFlags::POLLIN) {
+            Ok(Wait::Readable)
+        } else if events.contains(PollFlags::POLLHUP) {
+            // reads fail and poll returns at once until a host opens the port
+            thread::sleep(Duration::from_millis(timeout_ms.into()));
+            Ok(Wait::Closed)
+        } else {
+            Ok(Wait::Timeout)
+        }
+    }
+
+    /// Baud rate the host set on its end, like the CDC line coding.
+    pub fn baud(&self) -> nix::Result<u32> {
+        let termios = tcgetattr(&self.master)?;
+        Ok(baud_rate(cfgetospeed(&termios)))
+    }
+}
+
+fn baud_rate(rate: BaudRate) -> u32 {
+    match rate {
+        BaudRate::B9600 => 9_600,
+        BaudRate::B19200 => 19_200,
+        BaudRate::B38400 => 38_400,
+        BaudRate::B57600 => 57_600,
+        BaudRate::B115200 => 115_200,
+        BaudRate::B230400 => 230_400,
+        BaudRate::B460800 => 460_800,
+        BaudRate::B500000 => 500_000,
+        BaudRate::B921600 => 921_600,
+        BaudRate::B1000000 => 1_000_000,
+        BaudRate::B1500000 => 1_500_000,
+        BaudRate::B2000000 => 2_000_000,
+        BaudRate::B3000000 => 3_000_000,
+        // anything else doesn't reach the chips
+        _ => 0,
+    }
+}
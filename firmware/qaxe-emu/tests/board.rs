@@ -0,0 +1,153 @@
+use qaxe_core::framing::{Protocol, MAX_FRAME};
+use qaxe_core::protobuf::coms::{QRequest, QResponse, QState};
+use qaxe_core::protobuf::{deserialize_from_slice, serialize_into_slice};
+use qaxe_core::relay::ResponseSync;
+use qaxe_core::rpc::{Commands, Device, MAX_RESPONSE_DATA};
+use qaxe_core::sim::{
+    command, Chain, Model, CMD_READ, DEFAULT_BAUD, GROUP_ALL, REG_CHIP_ID, TYPE_CMD,
+};
+use qaxe_emu::board::Board;
+use qaxe_emu::control::{handle_frame, Session};
+
+fn board() -> Board {
+    Board::new(Chain::new(Model::Bm1368, 2))
+}
+
+/// Run the board for `ms` in steps like the control loop does.
+fn run(board: &mut Board, from_ms: u64, ms: u64) {
+    for t in (from_ms..=from_ms + ms).step_by(50) {
+        board.tick(t);
+    }
+}
+
+/// Send a request through the frame handler and decode the state it returns.
+fn status(board: &mut Board) -> QState {
+    let request = QRequest {
+        id: 7,
+        op: Commands::Status as i32,
+        data: &[],
+        ..Default::default()
+    };
+    let mut frame = [0u8; MAX_FRAME];
+    let len = serialize_into_slice(&request, &mut frame).unwrap();
+
+    let mut data = [0u8; MAX_RESPONSE_DATA];
+    let response: QResponse = handle_frame(board, &frame[..len], &mut data);
+    assert_eq!(response.id, 7);
+    assert_eq!(response.error, 0);
+    deserialize_from_slice(response.data).unwrap()
+}
+
+fn chip_ids(board: &mut Board) -> usize {
+    let mut sync = ResponseSync::new();
+    if board.chips_running() {
+        let read = command(TYPE_CMD | GROUP_ALL | CMD_READ, &[0, REG_CHIP_ID]);
+        board.chain().receive(DEFAULT_BAUD, &read);
+    }
+    board
+        .chain()
+        .transmit(DEFAULT_BAUD)
+        .into_iter()
+        .filter_map(|byte| sync.push(byte))
+        .count()
+}
+
+#[test]
+fn fans_cool_the_chips() {
+    let mut slow = board();
+    let mut fast = board();
+    for (board, duty) in [(&mut slow, 0), (&mut fast, 1000)] {
+        board.set_pwm(duty, duty);
+        board.reset();
+        run(board, 0, 120_000);
+    }
+
+    let (slow, fast) = (status(&mut slow), status(&mut fast));
+    assert!(fast.temp1 < slow.temp1);
+    assert!(fast.temp2 < slow.temp2);
+    // without fans the chips get close to ambient + 60°C
+    assert!(slow.temp1 > 80 * 16);
+}
+
+#[test]
+fn reset_sequence_gates_the_chain() {
+    let mut board = board();
+    assert_eq!(chip_ids(&mut board), 0);
+
+    board.reset();
+    run(&mut board, 0, 200);
+    assert_eq!(status(&mut board).pgood_1v2, 0);
+    assert_eq!(chip_ids(&mut board), 0);
+
+    run(&mut board, 250, 750);
+    assert_eq!(status(&mut board).pgood_1v2, 1);
+    assert_eq!(chip_ids(&mut board), 2);
+
+    board.shutdown();
+    assert_eq!(status(&mut board).pgood_1v2, 0);
+    assert_eq!(chip_ids(&mut board), 0);
+}
+
+#[test]
+fn terminals_get_the_shell() {
+    let mut board = board();
+    let mut session = Session::new();
+    let mut port = Vec::new();
+
+    // typed one key at a time
+    for &byte in b"fan 2 40\r\n" {
+        session.receive(&mut port, &mut board, &[byte]).unwrap();
+    }
+    assert_eq!(session.protocol(), Some(Protocol::Shell));
+    assert_eq!(
+        String::from_utf8(port).unwrap(),
+        "fan 2 40\r\nfan2 set to 40.0%\r\nqaxe> "
+    );
+    assert_eq!(status(&mut board).pwm2_target, 400);
+
+    // and leave it for protobuf requests
+    let mut port = Vec::new();
+    session.receive(&mut port, &mut board, b"exit\r").unwrap();
+    assert_eq!(session.protocol(), None);
+    let request = QRequest {
+        id: 3,
+        op: Commands::Status as i32,
+        ..Default::default()
+    };
+    let mut frame = [0u8; MAX_FRAME];
+    let len = serialize_into_slice(&request, &mut frame).unwrap();
+    let mut port = Vec::new();
+    session
+        .receive(&mut port, &mut board, &frame[..len])
+        .unwrap();
+    assert_eq!(session.protocol(), Some(Protocol::Protobuf));
+    let response: QResponse = deserialize_from_slice(&port).unwrap();
+    assert_eq!(response.id, 3);
+}
+
+#[test]
+fn scripts_get_json_rpc() {
+    let mut board = board();
+    let mut session = Session::new();
+    let mut port = Vec::new();
+
+    let requests = concat!(
+        r#"{"jsonrpc":"2.0","method":"control","params":{"pwm1":30,"pwm2":70}}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","method":"get_pwm_config","id":7}"#,
+        "\n",
+    );
+    session
+        .receive(&mut port, &mut board, requests.as_bytes())
+        .unwrap();
+    assert_eq!(session.protocol(), Some(Protocol::JsonRpc));
+    assert_eq!(status(&mut board).pwm2_target, 700);
+    // only the request with an id is answered
+    let response = String::from_utf8(port).unwrap();
+    assert!(
+        response.starts_with("{\"jsonrpc\":\"2.0\",\"result\":{\"frequency\":10000,"),
+        "{}",
+        response
+    );
+    assert!(response.ends_with(",\"id\":7}\n"), "{}", response);
+}
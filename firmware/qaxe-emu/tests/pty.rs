@@ -0,0 +1,40 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use nix::libc::O_NOCTTY;
+use nix::sys::termios::{tcgetattr, LocalFlags};
+use qaxe_emu::pty::{Pty, Wait};
+
+fn open_host(path: &Path) -> File {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(O_NOCTTY)
+        .open(path)
+        .unwrap()
+}
+
+#[test]
+fn hosts_closing_the_port_are_seen() {
+    let mut pty = Pty::open().unwrap();
+    assert_eq!(pty.wait(0).unwrap(), Wait::Closed);
+
+    let mut host = open_host(&pty.path);
+    assert_eq!(pty.wait(0).unwrap(), Wait::Timeout);
+
+    // what the host sent before leaving is still read
+    host.write_all(b"exi").unwrap();
+    drop(host);
+    assert_eq!(pty.wait(0).unwrap(), Wait::Readable);
+    let mut data = [0u8; 8];
+    assert_eq!(pty.master.read(&mut data).unwrap(), 3);
+    assert_eq!(pty.wait(0).unwrap(), Wait::Closed);
+
+    // the next host gets the raw port again
+    let host = open_host(&pty.path);
+    let termios = tcgetattr(&host).unwrap();
+    assert!(!termios.local_flags.contains(LocalFlags::ICANON));
+    assert_eq!(pty.wait(0).unwrap(), Wait::Timeout);
+}
@@ -0,0 +1,182 @@
+//! The `FileDescriptorProto` of a proto file as `protoc` serializes it into
+//! the python bindings, to tell whether they were generated from it.
+
+use crate::{Field, Message, Proto};
+
+// FileDescriptorProto
+const FILE_NAME: u32 = 1;
+const FILE_MESSAGE_TYPE: u32 = 4;
+const FILE_SYNTAX: u32 = 12;
+// DescriptorProto
+const MESSAGE_NAME: u32 = 1;
+const MESSAGE_FIELD: u32 = 2;
+const MESSAGE_ONEOF_DECL: u32 = 8;
+// FieldDescriptorProto
+const FIELD_NAME: u32 = 1;
+const FIELD_NUMBER: u32 = 3;
+const FIELD_LABEL: u32 = 4;
+const FIELD_TYPE: u32 = 5;
+const FIELD_TYPE_NAME: u32 = 6;
+const FIELD_ONEOF_INDEX: u32 = 9;
+// OneofDescriptorProto
+const ONEOF_NAME: u32 = 1;
+
+const LABEL_OPTIONAL: u64 = 1;
+const LABEL_REPEATED: u64 = 3;
+const TYPE_MESSAGE: u64 = 11;
+
+fn field_type(typ: &str) -> u64 {
+    match typ {
+        "int64" => 3,
+        "uint64" => 4,
+        "int32" => 5,
+        "bool" => 8,
+        "string" => 9,
+        "bytes" => 12,
+        "uint32" => 13,
+        "sint32" => 17,
+        _ => TYPE_MESSAGE,
+    }
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn put_uint(out: &mut Vec<u8>, number: u32, value: u64) {
+    put_varint(out, (number as u64) << 3);
+    put_varint(out, value);
+}
+
+fn put_bytes(out: &mut Vec<u8>, number: u32, data: &[u8]) {
+    put_varint(out, ((number as u64) << 3) | 2);
+    put_varint(out, data.len() as u64);
+    out.extend_from_slice(data);
+}
+
+fn field(message: &Message, field: &Field) -> Vec<u8> {
+    let mut out = Vec::new();
+    put_bytes(&mut out, FIELD_NAME, field.name.as_bytes());
+    put_uint(&mut out, FIELD_NUMBER, field.number as u64);
+    let label = if field.repeated {
+        LABEL_REPEATED
+    } else {
+        LABEL_OPTIONAL
+    };
+    put_uint(&mut out, FIELD_LABEL, label);
+    let typ = field_type(&field.typ);
+    put_uint(&mut out, FIELD_TYPE, typ);
+    if typ == TYPE_MESSAGE {
+        put_bytes(
+            &mut out,
+            FIELD_TYPE_NAME,
+            format!(".{}", field.typ).as_bytes(),
+        );
+    }
+    if let Some(oneof) = &field.oneof {
+        let index = message.oneofs.iter().position(|o| o == oneof).unwrap();
+        put_uint(&mut out, FIELD_ONEOF_INDEX, index as u64);
+    }
+    out
+}
+
+/// Serialize the descriptor of `proto` like `protoc` does.
+pub fn file_descriptor(file_name: &str, proto: &Proto) -> Vec<u8> {
+    let mut out = Vec::new();
+    put_bytes(&mut out, FILE_NAME, file_name.as_bytes());
+    for message in &proto.messages {
+        let mut m = Vec::new();
+        put_bytes(&mut m, MESSAGE_NAME, message.name.as_bytes());
+        for f in &message.fields {
+            put_bytes(&mut m, MESSAGE_FIELD, &field(message, f));
+        }
+        for oneof in &message.oneofs {
+            let mut o = Vec::new();
+            put_bytes(&mut o, ONEOF_NAME, oneof.as_bytes());
+            put_bytes(&mut m, MESSAGE_ONEOF_DECL, &o);
+        }
+        put_bytes(&mut out, FILE_MESSAGE_TYPE, &m);
+    }
+    put_bytes(&mut out, FILE_SYNTAX, b"proto3");
+    out
+}
+
+/// Undo the escaping of a python bytes literal.
+fn unescape(literal: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut bytes = literal.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        let c = bytes.next().ok_or("escape at the end of the literal")?;
+        out.push(match c {
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'\\' | b'\'' | b'"' => c,
+            b'x' => {
+                let hex = [bytes.next().unwrap_or(0), bytes.next().unwrap_or(0)];
+                std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or("invalid \\x escape")?
+            }
+            b'0'..=b'7' => {
+                // up to three octal digits
+                let mut value = (c - b'0') as u32;
+                for _ in 0..2 {
+                    match bytes.clone().next() {
+                        Some(d @ b'0'..=b'7') => {
+                            value = value * 8 + (d - b'0') as u32;
+                            bytes.next();
+                        }
+                        _ => break,
+                    }
+                }
+                value as u8
+            }
+            _ => return Err(format!("unsupported escape \\{}", c as char)),
+        });
+    }
+    Ok(out)
+}
+
+/// The serialized descriptor the python bindings were generated from.
+pub fn python_descriptor(source: &str) -> Result<Vec<u8>, String> {
+    const START: &str = "serialized_pb=b'";
+    let start = source.find(START).ok_or("no serialized_pb")? + START.len();
+    let rest = &source[start..];
+
+    // the literal ends at the first quote that isn't escaped
+    let mut end = None;
+    let mut escaped = false;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '\'' if !escaped => {
+                end = Some(i);
+                break;
+            }
+            _ => escaped = false,
+        }
+    }
+    unescape(&rest[..end.ok_or("unterminated serialized_pb")?])
+}
+
+/// Check that the python bindings were generated from `proto`.
+pub fn check_python(file_name: &str, proto: &Proto, source: &str) -> Result<(), String> {
+    if python_descriptor(source)? != file_descriptor(file_name, proto) {
+        return Err(format!(
+            "the python bindings weren't generated from this {}, regenerate them with \
+             `protoc --python_out=. {}`",
+            file_name, file_name
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,601 @@
+//! Generates the no-heap Rust bindings of `coms.proto` for `qaxe_core::protobuf::wire`.
+//!
+//! Handles the proto3 subset the protocol uses: messages with scalar, bytes,
+//! string and message fields, repeated messages and oneofs of messages. A
+//! oneof becomes an enum like pb-rs makes them, `mod_QRequest::OneOfbody`
+//! for `QRequest.body`, with a `None` variant. Repeated fields need a
+//! capacity in the options file, one line per field in the style of nanopb:
+//! ```text
+//! QSensors.sensors max_count:4
+//! ```
+//!
+//! The module is `include!`d, the allow attributes go on the `mod` around it.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+mod descriptor;
+
+pub use descriptor::{check_python, file_descriptor, python_descriptor};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub typ: String,
+    pub number: u32,
+    pub repeated: bool,
+    /// the oneof the field is part of
+    pub oneof: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub name: String,
+    /// in the order of the proto file, including the fields of the oneofs
+    pub fields: Vec<Field>,
+    pub oneofs: Vec<String>,
+}
+
+impl Message {
+    fn oneof_fields<'m>(&'m self, oneof: &'m str) -> impl Iterator<Item = &'m Field> {
+        self.fields
+            .iter()
+            .filter(move |f| f.oneof.as_deref() == Some(oneof))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Proto {
+    pub messages: Vec<Message>,
+}
+
+/// Capacities of the repeated fields by `Message.field`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    pub max_count: HashMap<String, usize>,
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for line in text.lines() {
+        let line = line.split("//").next().unwrap_or_default();
+        let mut chars = line.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c.is_alphanumeric() || c == '_' || c == '.' {
+                let mut token = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '.') {
+                        break;
+                    }
+                    token.push(c);
+                    chars.next();
+                }
+                tokens.push(token);
+            } else if c == '"' {
+                chars.next();
+                let token: String = chars.by_ref().take_while(|&c| c != '"').collect();
+                tokens.push(format!("\"{}\"", token));
+            } else {
+                tokens.push(c.to_string());
+                chars.next();
+            }
+        }
+    }
+    tokens
+}
+
+struct Tokens {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Tokens {
+    fn next(&mut self) -> Result<String, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or("unexpected end of file")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(format!("expected `{}`, found `{}`", expected, token));
+        }
+        Ok(())
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+}
+
+const SCALARS: &[&str] = &[
+    "int32", "int64", "uint32", "uint64", "sint32", "bool", "bytes", "string",
+];
+
+impl Proto {
+    pub fn parse(text: &str) -> Result<Proto, String> {
+        let mut t = Tokens {
+            tokens: tokenize(text),
+            pos: 0,
+        };
+        let mut proto = Proto::default();
+
+        while !t.done() {
+            match t.next()?.as_str() {
+                "syntax" => {
+                    t.expect("=")?;
+                    let syntax = t.next()?;
+                    if syntax != "\"proto3\"" {
+                        return Err(format!("unsupported syntax {}", syntax));
+                    }
+                    t.expect(";")?;
+                }
+                "message" => proto.messages.push(parse_message(&mut t)?),
+                token => return Err(format!("unsupported `{}`", token)),
+            }
+        }
+
+        for message in &proto.messages {
+            for field in &message.fields {
+                let scalar = SCALARS.contains(&field.typ.as_str());
+                if !scalar && proto.message(&field.typ).is_none() {
+                    return Err(format!("{}.{}: unknown type", message.name, field.name));
+                }
+                if scalar && field.oneof.is_some() {
+                    return Err(format!(
+                        "{}.{}: only messages are supported in a oneof",
+                        message.name, field.name
+                    ));
+                }
+            }
+        }
+        Ok(proto)
+    }
+
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages.iter().find(|m| m.name == name)
+    }
+
+    /// Whether the Rust type of the message borrows from the input.
+    fn borrows(&self, message: &Message) -> bool {
+        message.fields.iter().any(|f| self.field_borrows(f))
+    }
+
+    fn field_borrows(&self, field: &Field) -> bool {
+        match field.typ.as_str() {
+            "bytes" | "string" => true,
+            typ => self.message(typ).is_some_and(|m| self.borrows(m)),
+        }
+    }
+
+    fn oneof_borrows(&self, message: &Message, oneof: &str) -> bool {
+        message.oneof_fields(oneof).any(|f| self.field_borrows(f))
+    }
+}
+
+fn parse_message(t: &mut Tokens) -> Result<Message, String> {
+    let mut message = Message {
+        name: t.next()?,
+        fields: Vec::new(),
+        oneofs: Vec::new(),
+    };
+    t.expect("{")?;
+
+    let mut oneof = None;
+    loop {
+        let mut token = t.next()?;
+        if token == "}" {
+            if oneof.take().is_some() {
+                continue;
+            }
+            return Ok(message);
+        }
+        if token == "oneof" && oneof.is_none() {
+            let name = t.next()?;
+            t.expect("{")?;
+            message.oneofs.push(name.clone());
+            oneof = Some(name);
+            continue;
+        }
+        let repeated = token == "repeated";
+        if repeated && oneof.is_some() {
+            return Err(format!("{}: repeated field in a oneof", message.name));
+        }
+        if repeated {
+            token = t.next()?;
+        }
+        let name = t.next()?;
+        t.expect("=")?;
+        let number = t
+            .next()?
+            .parse()
+            .map_err(|_| format!("{}.{}: invalid field number", message.name, name))?;
+        t.expect(";")?;
+
+        message.fields.push(Field {
+            name,
+            typ: token,
+            number,
+            repeated,
+            oneof: oneof.clone(),
+        });
+    }
+}
+
+impl Options {
+    pub fn parse(text: &str) -> Result<Options, String> {
+        let mut options = Options::default();
+        for line in text.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let field = parts.next().unwrap_or_default();
+            for option in parts {
+                match option.split_once(':') {
+                    Some(("max_count", count)) => {
+                        let count = count
+                            .parse()
+                            .map_err(|_| format!("{}: invalid max_count", field))?;
+                        options.max_count.insert(field.to_string(), count);
+                    }
+                    _ => return Err(format!("{}: unsupported option {}", field, option)),
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn sizeof_varint(value: u64) -> usize {
+    (64 - value.max(1).leading_zeros() as usize).div_ceil(7)
+}
+
+struct Kind {
+    rust: &'static str,
+    read: &'static str,
+    write: &'static str,
+    /// size of the value `$`
+    size: &'static str,
+    zero: &'static str,
+}
+
+fn scalar(typ: &str) -> Option<Kind> {
+    let (rust, size, zero) = match typ {
+        "int32" => ("i32", "sizeof_int32($)", "0i32"),
+        "int64" => ("i64", "sizeof_varint($ as u64)", "0i64"),
+        "uint32" => ("u32", "sizeof_varint($ as u64)", "0u32"),
+        "uint64" => ("u64", "sizeof_varint($)", "0u64"),
+        "sint32" => ("i32", "sizeof_sint32($)", "0i32"),
+        "bool" => ("bool", "1", "false"),
+        "bytes" => ("&'a [u8]", "sizeof_len($.len())", ""),
+        "string" => ("&'a str", "sizeof_len($.len())", ""),
+        _ => return None,
+    };
+    let (read, write) = match typ {
+        "int32" => ("read_int32", "write_int32"),
+        "int64" => ("read_int64", "write_int64"),
+        "uint32" => ("read_uint32", "write_uint32"),
+        "uint64" => ("read_uint64", "write_uint64"),
+        "sint32" => ("read_sint32", "write_sint32"),
+        "bool" => ("read_bool", "write_bool"),
+        "bytes" => ("read_bytes", "write_bytes"),
+        _ => ("read_string", "write_string"),
+    };
+    Some(Kind {
+        rust,
+        read,
+        write,
+        size,
+        zero,
+    })
+}
+
+/// Generate the Rust module for `proto`.
+pub fn generate(file_name: &str, proto: &Proto, options: &Options) -> Result<String, String> {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Automatically generated rust module for '{}' file by qaxe-protogen",
+        file_name
+    );
+    out.push_str(
+        r#"
+use heapless::Vec;
+use super::wire::*;
+"#,
+    );
+
+    for message in &proto.messages {
+        generate_message(&mut out, proto, options, message)?;
+    }
+    Ok(out)
+}
+
+fn generate_message(
+    out: &mut String,
+    proto: &Proto,
+    options: &Options,
+    message: &Message,
+) -> Result<(), String> {
+    let lifetime = if proto.borrows(message) { "<'a>" } else { "" };
+    let name = &message.name;
+    let plain: Vec<&Field> = message
+        .fields
+        .iter()
+        .filter(|f| f.oneof.is_none())
+        .collect();
+    let oneof_type = |oneof: &str| {
+        let lifetime = if proto.oneof_borrows(message, oneof) {
+            "<'a>"
+        } else {
+            ""
+        };
+        format!("mod_{}::OneOf{}{}", name, oneof, lifetime)
+    };
+    let variant = |field: &Field| {
+        format!(
+            "mod_{}::OneOf{}::{}",
+            name,
+            field.oneof.as_ref().unwrap(),
+            field.name
+        )
+    };
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#[allow(clippy::derive_partial_eq_without_eq)]");
+    let _ = writeln!(out, "#[derive(Debug, Default, PartialEq, Clone)]");
+    let _ = writeln!(out, "pub struct {}{} {{", name, lifetime);
+    for field in &plain {
+        let typ = match scalar(&field.typ) {
+            Some(kind) if field.repeated => {
+                return Err(format!(
+                    "{}.{}: repeated {} is not supported",
+                    name, field.name, kind.rust
+                ))
+            }
+            Some(kind) => kind.rust.to_string(),
+            None => {
+                let typ = message_type(proto, field);
+                if field.repeated {
+                    let key = format!("{}.{}", name, field.name);
+                    let count = options
+                        .max_count
+                        .get(&key)
+                        .ok_or(format!("{}: repeated field needs a max_count", key))?;
+                    format!("Vec<{}, {}>", typ, count)
+                } else {
+                    format!("Option<{}>", typ)
+                }
+            }
+        };
+        let _ = writeln!(out, "    pub {}: {},", field.name, typ);
+    }
+    for oneof in &message.oneofs {
+        let _ = writeln!(out, "    pub {}: {},", oneof, oneof_type(oneof));
+    }
+    let _ = writeln!(out, "}}");
+
+    let tag = |field: &Field| {
+        let wire_type = match scalar(&field.typ) {
+            Some(_) if field.typ != "bytes" && field.typ != "string" => 0,
+            _ => 2,
+        };
+        (field.number << 3) | wire_type
+    };
+
+    // decoding
+    let _ = writeln!(out);
+    let _ = writeln!(out, "impl<'a> MessageRead<'a> for {}{} {{", name, lifetime);
+    let _ = writeln!(
+        out,
+        "    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {{"
+    );
+    let mutable = if message.fields.is_empty() {
+        ""
+    } else {
+        "mut "
+    };
+    let _ = writeln!(out, "        let {}msg = Self::default();", mutable);
+    let _ = writeln!(out, "        while !r.is_eof() {{");
+    let _ = writeln!(out, "            match r.next_tag(bytes) {{");
+    for field in &message.fields {
+        let tag = tag(field);
+        let _ = match scalar(&field.typ) {
+            Some(kind) => writeln!(
+                out,
+                "                Ok({}) => msg.{} = r.{}(bytes)?,",
+                tag, field.name, kind.read
+            ),
+            None if field.oneof.is_some() => writeln!(
+                out,
+                "                Ok({}) => msg.{} = {}(r.read_message::<{}>(bytes)?),",
+                tag,
+                field.oneof.as_ref().unwrap(),
+                variant(field),
+                field.typ
+            ),
+            None if field.repeated => writeln!(
+                out,
+                "                Ok({}) => msg.{}.push(r.read_message::<{}>(bytes)?).map_err(|_| Error::RepeatedFieldFull)?,",
+                tag, field.name, field.typ
+            ),
+            None => writeln!(
+                out,
+                "                Ok({}) => msg.{} = Some(r.read_message::<{}>(bytes)?),",
+                tag, field.name, field.typ
+            ),
+        };
+    }
+    let _ = writeln!(
+        out,
+        "                Ok(t) => {{ r.read_unknown(bytes, t)?; }}"
+    );
+    let _ = writeln!(out, "                Err(e) => return Err(e),");
+    let _ = writeln!(out, "            }}");
+    let _ = writeln!(out, "        }}");
+    let _ = writeln!(out, "        Ok(msg)");
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out, "}}");
+
+    // encoding
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "impl{} MessageWrite for {}{} {{",
+        lifetime, name, lifetime
+    );
+    let _ = writeln!(out, "    fn get_size(&self) -> usize {{");
+    let _ = writeln!(out, "        0");
+    for field in &plain {
+        let tag_len = sizeof_varint(tag(field) as u64);
+        let f = &field.name;
+        let size = |kind: &Kind| kind.size.replace('$', &format!("self.{}", f));
+        let _ = match scalar(&field.typ) {
+            Some(kind) if kind.zero.is_empty() => writeln!(
+                out,
+                "        + if self.{f}.is_empty() {{ 0 }} else {{ {} + {} }}",
+                tag_len,
+                size(&kind)
+            ),
+            Some(kind) => writeln!(
+                out,
+                "        + if self.{f} == {} {{ 0 }} else {{ {} + {} }}",
+                kind.zero,
+                tag_len,
+                size(&kind)
+            ),
+            None if field.repeated => writeln!(
+                out,
+                "        + self.{f}.iter().map(|s| {} + sizeof_len(s.get_size())).sum::<usize>()",
+                tag_len
+            ),
+            None => writeln!(
+                out,
+                "        + self.{f}.as_ref().map_or(0, |m| {} + sizeof_len(m.get_size()))",
+                tag_len
+            ),
+        };
+    }
+    for oneof in &message.oneofs {
+        let _ = writeln!(out, "        + match self.{} {{", oneof);
+        for field in message.oneof_fields(oneof) {
+            let _ = writeln!(
+                out,
+                "            {}(ref m) => {} + sizeof_len(m.get_size()),",
+                variant(field),
+                sizeof_varint(tag(field) as u64)
+            );
+        }
+        let _ = writeln!(out, "            mod_{}::OneOf{}::None => 0,", name, oneof);
+        let _ = writeln!(out, "        }}");
+    }
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out);
+    let writer = if message.fields.is_empty() { "_w" } else { "w" };
+    let _ = writeln!(
+        out,
+        "    fn write_message(&self, {}: &mut Writer) -> Result<()> {{",
+        writer
+    );
+    for field in &plain {
+        let tag = tag(field);
+        let f = &field.name;
+        let _ = match scalar(&field.typ) {
+            Some(kind) if kind.zero.is_empty() => writeln!(
+                out,
+                "        if !self.{f}.is_empty() {{ w.write_with_tag({}, |w| w.{}(self.{f}))?; }}",
+                tag, kind.write
+            ),
+            Some(kind) => writeln!(
+                out,
+                "        if self.{f} != {} {{ w.write_with_tag({}, |w| w.{}(self.{f}))?; }}",
+                kind.zero, tag, kind.write
+            ),
+            None if field.repeated => writeln!(
+                out,
+                "        for s in &self.{f} {{ w.write_with_tag({}, |w| w.write_message(s))?; }}",
+                tag
+            ),
+            None => writeln!(
+                out,
+                "        if let Some(ref s) = self.{f} {{ w.write_with_tag({}, |w| w.write_message(s))?; }}",
+                tag
+            ),
+        };
+    }
+    for oneof in &message.oneofs {
+        let _ = writeln!(out, "        match self.{} {{", oneof);
+        for field in message.oneof_fields(oneof) {
+            let _ = writeln!(
+                out,
+                "            {}(ref m) => {{ w.write_with_tag({}, |w| w.write_message(m))? }}",
+                variant(field),
+                tag(field)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "            mod_{}::OneOf{}::None => {{}}",
+            name, oneof
+        );
+        let _ = writeln!(out, "        }}");
+    }
+    let _ = writeln!(out, "        Ok(())");
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out, "}}");
+
+    // the enums of the oneofs
+    if !message.oneofs.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "pub mod mod_{} {{", name);
+        let _ = writeln!(out);
+        let _ = writeln!(out, "use super::*;");
+        for oneof in &message.oneofs {
+            let lifetime = if proto.oneof_borrows(message, oneof) {
+                "<'a>"
+            } else {
+                ""
+            };
+            let _ = writeln!(out);
+            let _ = writeln!(out, "#[derive(Debug, PartialEq, Clone)]");
+            let _ = writeln!(out, "pub enum OneOf{}{} {{", oneof, lifetime);
+            for field in message.oneof_fields(oneof) {
+                let _ = writeln!(out, "    {}({}),", field.name, message_type(proto, field));
+            }
+            let _ = writeln!(out, "    None,");
+            let _ = writeln!(out, "}}");
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "impl{} Default for OneOf{}{} {{",
+                lifetime, oneof, lifetime
+            );
+            let _ = writeln!(out, "    fn default() -> Self {{");
+            let _ = writeln!(out, "        OneOf{}::None", oneof);
+            let _ = writeln!(out, "    }}");
+            let _ = writeln!(out, "}}");
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(out, "}}");
+    }
+    Ok(())
+}
+
+/// Rust type of a message field, with the lifetime if it borrows.
+fn message_type(proto: &Proto, field: &Field) -> String {
+    let nested = proto.message(&field.typ).unwrap();
+    if proto.borrows(nested) {
+        format!("{}<'a>", field.typ)
+    } else {
+        field.typ.clone()
+    }
+}
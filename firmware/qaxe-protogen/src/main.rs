@@ -0,0 +1,33 @@
+use std::fs;
+use std::process::exit;
+
+use qaxe_protogen::{generate, Options, Proto};
+
+const USAGE: &str = "usage: qaxe-protogen <file.proto> <file.options> <out.rs>";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [proto, options, out] = &args[..] else {
+        eprintln!("{}", USAGE);
+        exit(2);
+    };
+
+    let result = (|| {
+        let text = fs::read_to_string(proto).map_err(|e| format!("{}: {}", proto, e))?;
+        let proto_file = Proto::parse(&text)?;
+        let text = fs::read_to_string(options).map_err(|e| format!("{}: {}", options, e))?;
+        let options = Options::parse(&text)?;
+
+        let name = std::path::Path::new(proto)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let code = generate(&name, &proto_file, &options)?;
+        fs::write(out, code).map_err(|e| format!("{}: {}", out, e))
+    })();
+
+    if let Err(e) = result {
+        eprintln!("qaxe-protogen: {}", e);
+        exit(1);
+    }
+}
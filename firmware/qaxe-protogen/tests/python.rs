@@ -0,0 +1,39 @@
+use qaxe_protogen::{check_python, Proto};
+
+// as generated by protoc for the proto below
+const LOG_PB2: &str = r#"
+DESCRIPTOR = _descriptor.FileDescriptor(
+  name='log.proto',
+  package='',
+  syntax='proto3',
+  serialized_options=None,
+  create_key=_descriptor._internal_create_key,
+  serialized_pb=b'\n\tlog.proto\"%\n\x04QLog\x12\x0c\n\x04\x64\x61ta\x18\x01 \x01(\x0c\x12\x0f\n\x07\x64ropped\x18\x02 \x01(\x05\x62\x06proto3'
+)
+"#;
+
+const LOG_PROTO: &str = r#"
+syntax = "proto3";
+
+message QLog {
+    bytes data = 1;
+    int32 dropped = 2;
+}
+"#;
+
+#[test]
+fn current_bindings_pass() {
+    let proto = Proto::parse(LOG_PROTO).unwrap();
+    assert_eq!(check_python("log.proto", &proto, LOG_PB2), Ok(()));
+}
+
+#[test]
+fn stale_bindings_fail() {
+    let changed = LOG_PROTO.replace("dropped = 2", "dropped = 3");
+    let proto = Proto::parse(&changed).unwrap();
+    assert!(check_python("log.proto", &proto, LOG_PB2).is_err());
+
+    let added = LOG_PROTO.replace("}", "    QLog next = 3;\n}");
+    let proto = Proto::parse(&added).unwrap();
+    assert!(check_python("log.proto", &proto, LOG_PB2).is_err());
+}